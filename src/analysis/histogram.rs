@@ -0,0 +1,111 @@
+use std::fmt;
+
+use crate::rank;
+use crate::structs::is_printable_ascii;
+
+/// width of the longest bar displayed by a histogram
+const BAR_WIDTH: usize = 50;
+
+/********************************* HISTOGRAM *********************************/
+
+/// number of occurrences of each byte of an input
+#[derive(Debug, PartialEq, Clone)]
+pub struct Histogram {
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn new<T: AsRef<[u8]> + ?Sized>(input: &T) -> Self {
+        let mut counts = vec![0; 256];
+        for byte in input.as_ref().iter() {
+            counts[*byte as usize] += 1;
+        }
+        Self { counts }
+    }
+
+    /// number of occurrences of the byte
+    pub fn count(&self, byte: u8) -> usize {
+        self.counts[byte as usize]
+    }
+
+    /// number of bytes of the input
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// number of different bytes in the input
+    pub fn distinct(&self) -> usize {
+        self.counts.iter().filter(|count| **count > 0).count()
+    }
+
+    /// bytes occurring in the input, along with their number of occurrences
+    pub fn iter(&self) -> impl Iterator<Item = (u8, usize)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(byte, count)| (byte as u8, *count))
+    }
+
+    /// the `n` most frequent bytes, by decreasing number of occurrences
+    pub fn most_common(&self, n: usize) -> Vec<(u8, usize)> {
+        let mut counts: Vec<(u8, usize)> = self.iter().collect();
+        rank::descending(&mut counts, |(_, count)| *count as f64);
+        counts.truncate(n);
+        counts
+    }
+}
+
+/// one row per byte occurring in the input, with a bar proportional to its count
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max = self.counts.iter().max().copied().unwrap_or(0).max(1);
+        for (byte, count) in self.iter() {
+            let symbol = if is_printable_ascii(byte) {
+                byte as char
+            } else {
+                '.'
+            };
+            writeln!(
+                f,
+                "{:02x} {} {:>8} {}",
+                byte,
+                symbol,
+                count,
+                "#".repeat((count * BAR_WIDTH).div_ceil(max))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Histogram;
+
+    #[test]
+    fn counts() {
+        let histogram = Histogram::new(b"hello world");
+        assert_eq!(histogram.count(b'l'), 3);
+        assert_eq!(histogram.count(b'z'), 0);
+        assert_eq!(histogram.total(), 11);
+        assert_eq!(histogram.distinct(), 8);
+        assert_eq!(histogram.most_common(2), vec![(b'l', 3), (b'o', 2)]);
+    }
+
+    #[test]
+    fn display() {
+        let histogram = Histogram::new(b"aab\x00");
+        assert_eq!(
+            histogram.to_string(),
+            format!(
+                "00 .        1 {}\n61 a        2 {}\n62 b        1 {}\n",
+                "#".repeat(25),
+                "#".repeat(50),
+                "#".repeat(25)
+            )
+        );
+    }
+}
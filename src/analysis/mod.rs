@@ -0,0 +1,10 @@
+mod histogram;
+mod report;
+mod statistics;
+
+pub use histogram::Histogram;
+pub use report::{Guess, Report};
+pub use statistics::{
+    entropy, index_of_coincidence, key_period, periodic_index_of_coincidence, rank_periods,
+    repeated_blocks,
+};
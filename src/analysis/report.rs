@@ -0,0 +1,194 @@
+use std::fmt;
+
+use super::{entropy, index_of_coincidence, key_period, rank_periods, repeated_blocks, Histogram};
+use crate::structs::is_text;
+
+/// block size used to look for repeated blocks (the one of AES)
+const BLOCK_SIZE: usize = 16;
+/// periods ranked by the report
+const MAX_PERIOD: usize = 40;
+/// proportion of printable bytes above which the input is deemed text
+const TEXT_RATIO: f64 = 0.95;
+/// index of coincidence above which columns are deemed (XORed) text rather than random
+const TEXT_COINCIDENCE: f64 = 0.03;
+/// ratio of the index of coincidence of the columns of the key period over the one of
+/// the whole input, above which the input is deemed XORed with a repeating key: XORing
+/// text with several key bytes flattens its distribution, but not the one of a column
+const PERIOD_PEAK: f64 = 1.5;
+
+/// most likely nature of an input, see `Report::guess`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Guess {
+    Empty,
+    /// printable text, used as is
+    Text,
+    /// text XORed with a key repeating every `period` bytes
+    XoredText {
+        period: usize,
+    },
+    /// repeated blocks, typical of a block cipher in ECB mode
+    EcbBlocks,
+    /// compressed, encrypted (in a chaining mode or with a stream cipher) or random data
+    HighEntropy,
+}
+
+/********************************** REPORT ***********************************/
+
+/// summary of the statistics of an input, to triage unknown blobs
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    pub length: usize,
+    /// Shannon entropy, in bits per byte
+    pub entropy: f64,
+    pub index_of_coincidence: f64,
+    /// proportion of printable ascii bytes (whitespace included)
+    pub printable_ratio: f64,
+    /// number of different bytes
+    pub distinct: usize,
+    /// number of 16-byte blocks repeating an earlier one
+    pub repeated_blocks: usize,
+    /// the most likely periods of a repeating key, with their index of coincidence
+    pub periods: Vec<(usize, f64)>,
+    /// the shortest period about as likely as the best one, with its index of coincidence
+    pub key_period: Option<(usize, f64)>,
+}
+
+impl Report {
+    pub fn new<T: AsRef<[u8]> + ?Sized>(input: &T) -> Self {
+        let input = input.as_ref();
+        let histogram = Histogram::new(input);
+        let printable = input.iter().filter(|b| is_text(**b)).count();
+
+        Self {
+            length: input.len(),
+            entropy: entropy(input),
+            index_of_coincidence: index_of_coincidence(input),
+            printable_ratio: if input.is_empty() {
+                0.0
+            } else {
+                printable as f64 / input.len() as f64
+            },
+            distinct: histogram.distinct(),
+            repeated_blocks: repeated_blocks(input, BLOCK_SIZE),
+            periods: rank_periods(input, 1..=MAX_PERIOD, 3),
+            key_period: key_period(input, 1..=MAX_PERIOD),
+        }
+    }
+
+    /// period of a repeating key whose columns have a much higher index of coincidence
+    /// than the whole input, unless repeated blocks explain it
+    fn period_peak(&self) -> Option<usize> {
+        match self.key_period {
+            Some((period, coincidence))
+                if period > 1
+                    && coincidence > TEXT_COINCIDENCE
+                    && coincidence > PERIOD_PEAK * self.index_of_coincidence
+                    && !(self.repeated_blocks > 0 && period % BLOCK_SIZE == 0) =>
+            {
+                Some(period)
+            }
+            _ => None,
+        }
+    }
+
+    /// most likely nature of the input, according to its statistics
+    ///
+    /// a periodic peak of the index of coincidence is looked for first, as text XORed
+    /// with a printable key (e.g. "ICE") is mostly printable
+    pub fn guess(&self) -> Guess {
+        if self.length == 0 {
+            return Guess::Empty;
+        }
+        if let Some(period) = self.period_peak() {
+            return Guess::XoredText { period };
+        }
+        if self.printable_ratio > TEXT_RATIO {
+            return Guess::Text;
+        }
+        if self.repeated_blocks > 0 {
+            return Guess::EcbBlocks;
+        }
+        match self.key_period {
+            Some((period, coincidence)) if coincidence > TEXT_COINCIDENCE => {
+                Guess::XoredText { period }
+            }
+            _ => Guess::HighEntropy,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "length:               {} bytes", self.length)?;
+        writeln!(f, "entropy:              {:.3} bits per byte", self.entropy)?;
+        writeln!(f, "index of coincidence: {:.4}", self.index_of_coincidence)?;
+        writeln!(
+            f,
+            "printable:            {:.1}%",
+            100.0 * self.printable_ratio
+        )?;
+        writeln!(f, "distinct bytes:       {}", self.distinct)?;
+        writeln!(f, "repeated blocks:      {}", self.repeated_blocks)?;
+        let periods: Vec<String> = self
+            .periods
+            .iter()
+            .map(|(period, coincidence)| format!("{} ({:.4})", period, coincidence))
+            .collect();
+        writeln!(f, "likely periods:       {}", periods.join(", "))?;
+        writeln!(f, "guess:                {:?}", self.guess())
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{Guess, Report};
+    use crate::random;
+    use crate::structs::Hex;
+
+    const TEXT: &str = include_str!("../../data/corpus/english.txt");
+
+    #[test]
+    fn guesses() {
+        assert_eq!(Report::new(b"").guess(), Guess::Empty);
+        assert_eq!(Report::new(TEXT).guess(), Guess::Text);
+
+        let xored = Hex::encode(TEXT).rolling_xor(&Hex::from(vec![0x91, 0x3c, 0xe7]));
+        assert_eq!(Report::new(&xored).guess(), Guess::XoredText { period: 3 });
+
+        // mostly printable, yet not plain text
+        for key in ["ICE", "YELLOW"].iter() {
+            let xored = Hex::encode(TEXT).rolling_xor(&Hex::encode(key));
+            let report = Report::new(&xored);
+            assert!(report.printable_ratio > 0.95, "{}", report);
+            assert_eq!(report.guess(), Guess::XoredText { period: key.len() });
+        }
+
+        let ecb = [&random::bytes(16)[..], &random::bytes(32)[..]].concat();
+        assert_eq!(Report::new(&ecb).guess(), Guess::EcbBlocks);
+
+        assert_eq!(
+            Report::new(&random::bytes(4096)).guess(),
+            Guess::HighEntropy
+        );
+    }
+
+    #[test]
+    fn display() {
+        let report = Report::new(b"aaaa");
+        assert_eq!(report.length, 4);
+        assert_eq!(report.distinct, 1);
+        assert_eq!(
+            report.to_string(),
+            "length:               4 bytes\n\
+             entropy:              0.000 bits per byte\n\
+             index of coincidence: 1.0000\n\
+             printable:            100.0%\n\
+             distinct bytes:       1\n\
+             repeated blocks:      0\n\
+             likely periods:       1 (1.0000), 2 (1.0000)\n\
+             guess:                Text\n"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use super::Histogram;
+use crate::rank;
+
+/// proportion of the best index of coincidence above which a period is as likely as the
+/// best one (multiples of a key length all stand out)
+const PERIOD_TOLERANCE: f64 = 0.9;
+
+/// Shannon entropy of the bytes of the input, in bits per byte: from 0 (a single byte
+/// repeated) to 8 (every byte equally frequent, e.g. compressed or encrypted data)
+pub fn entropy<T: AsRef<[u8]> + ?Sized>(input: &T) -> f64 {
+    let histogram = Histogram::new(input);
+    let total = histogram.total() as f64;
+    histogram
+        .iter()
+        .map(|(_, count)| {
+            let p = count as f64 / total;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+/// probability that two bytes drawn at random from the input are the same: about
+/// 1/256 for random data, much higher for text (about 0.066 for english letters)
+///
+/// 0 if the input holds less than two bytes
+pub fn index_of_coincidence<T: AsRef<[u8]> + ?Sized>(input: &T) -> f64 {
+    let histogram = Histogram::new(input);
+    let total = histogram.total();
+    if total < 2 {
+        return 0.0;
+    }
+
+    let coincidences: usize = histogram.iter().map(|(_, count)| count * (count - 1)).sum();
+    coincidences as f64 / (total * (total - 1)) as f64
+}
+
+/// mean index of coincidence of the columns of the input, the i-th column holding every
+/// `period`-th byte starting with the i-th one: it stands out when the period is a
+/// multiple of the length of a repeating key (e.g. Vigenère or repeating XOR)
+///
+/// panics if `period` is 0
+pub fn periodic_index_of_coincidence<T: AsRef<[u8]> + ?Sized>(input: &T, period: usize) -> f64 {
+    assert!(period > 0, "the period must be positive");
+    let input = input.as_ref();
+    let columns: Vec<Vec<u8>> = (0..period.min(input.len()))
+        .map(|column| input.iter().skip(column).step_by(period).copied().collect())
+        .collect();
+    if columns.is_empty() {
+        return 0.0;
+    }
+
+    columns.iter().map(index_of_coincidence).sum::<f64>() / columns.len() as f64
+}
+
+/// rank the periods by decreasing periodic index of coincidence, keeping the `top_n`
+/// best, periods leaving less than two bytes per column being skipped
+pub fn rank_periods<T: AsRef<[u8]> + ?Sized>(
+    input: &T,
+    periods: RangeInclusive<usize>,
+    top_n: usize,
+) -> Vec<(usize, f64)> {
+    let input = input.as_ref();
+    let mut periods: Vec<(usize, f64)> = periods
+        .filter(|period| *period > 0 && input.len() >= 2 * period)
+        .map(|period| (period, periodic_index_of_coincidence(input, period)))
+        .collect();
+
+    rank::descending(&mut periods, |(_, index)| *index);
+    periods.truncate(top_n);
+    periods
+}
+
+/// the shortest period about as likely as the best one (see `rank_periods`) with its
+/// index of coincidence: the most likely length of a repeating key
+pub fn key_period<T: AsRef<[u8]> + ?Sized>(
+    input: &T,
+    periods: RangeInclusive<usize>,
+) -> Option<(usize, f64)> {
+    let periods = rank_periods(input, periods, usize::MAX);
+    let best = periods.first()?.1;
+    periods
+        .into_iter()
+        .filter(|(_, coincidence)| *coincidence >= PERIOD_TOLERANCE * best)
+        .min_by_key(|(period, _)| *period)
+}
+
+/// number of blocks of `block_size` bytes that repeat an earlier block, typical of a
+/// block cipher in ECB mode (the last block is ignored if incomplete)
+///
+/// panics if `block_size` is 0
+pub fn repeated_blocks<T: AsRef<[u8]> + ?Sized>(input: &T, block_size: usize) -> usize {
+    assert!(block_size > 0, "blocks must hold at least one byte");
+    let mut seen = HashSet::new();
+    input
+        .as_ref()
+        .chunks_exact(block_size)
+        .filter(|block| !seen.insert(*block))
+        .count()
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{
+        entropy, index_of_coincidence, periodic_index_of_coincidence, rank_periods, repeated_blocks,
+    };
+    use crate::structs::Hex;
+
+    const TEXT: &str = include_str!("../../data/corpus/english.txt");
+
+    #[test]
+    fn shannon_entropy() {
+        assert_eq!(entropy(b"aaaa"), 0.0);
+        assert_eq!(entropy(b"abab"), 1.0);
+        assert_eq!(entropy(&(0..=255).collect::<Vec<u8>>()), 8.0);
+        assert_eq!(entropy(b""), 0.0);
+
+        let text = entropy(TEXT);
+        assert!(text > 3.5 && text < 5.0);
+    }
+
+    #[test]
+    fn coincidence() {
+        assert_eq!(index_of_coincidence(b"aaaa"), 1.0);
+        assert_eq!(index_of_coincidence(b"abcd"), 0.0);
+        assert_eq!(index_of_coincidence(b"aabb"), 4.0 / 12.0);
+        assert_eq!(index_of_coincidence(b"a"), 0.0);
+    }
+
+    #[test]
+    fn key_period() {
+        let ciphertext = Hex::encode(TEXT).rolling_xor(&Hex::encode("YELLOW"));
+        assert!(
+            periodic_index_of_coincidence(&ciphertext, 6)
+                > 2.0 * periodic_index_of_coincidence(&ciphertext, 5)
+        );
+
+        let periods = rank_periods(&ciphertext, 1..=10, 3);
+        assert_eq!(periods.len(), 3);
+        assert_eq!(periods[0].0, 6);
+        assert!(rank_periods(b"abc", 1..=10, 10)
+            .iter()
+            .all(|(p, _)| *p == 1));
+    }
+
+    #[test]
+    fn repeated() {
+        assert_eq!(repeated_blocks(b"YELLOW SUBMARINEYELLOW SUBMARINE!", 16), 1);
+        assert_eq!(repeated_blocks(b"abababab", 2), 3);
+        assert_eq!(repeated_blocks(b"abcdef", 2), 0);
+    }
+}
@@ -0,0 +1,455 @@
+use crate::analysis::key_period;
+use crate::random::Xorshift;
+use crate::structs::{ByteFrequency, NgramModel};
+
+/// letters by decreasing frequency in english text
+const ENGLISH_LETTER_ORDER: &[u8; 26] = b"etaoinshrdlcumwfgypbvkjxqz";
+/// number of random restarts of the hill climbing, see `break_substitution`
+const RESTARTS: usize = 4;
+
+/// multiplicative inverse modulo 26, if any
+fn inverse_mod_26(a: u8) -> Option<u8> {
+    (1..26u8).find(|x| (a as u32 * *x as u32) % 26 == 1)
+}
+
+/// letters of the text, lowercase
+fn letters(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_lowercase())
+        .collect()
+}
+
+/***************************** LETTER CIPHER *********************************/
+
+/// a classical cipher substituting each ascii letter (as an index from 0 to 25),
+/// depending on its position among the letters of the text; other characters are kept
+/// as is, and so is the case of the letters
+pub trait LetterCipher {
+    /// encrypt the `position`-th letter of the text
+    fn encrypt_letter(&self, position: usize, letter: u8) -> u8;
+
+    /// decrypt the `position`-th letter of the text
+    fn decrypt_letter(&self, position: usize, letter: u8) -> u8;
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        map_letters(plaintext, |position, letter| {
+            self.encrypt_letter(position, letter)
+        })
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        map_letters(ciphertext, |position, letter| {
+            self.decrypt_letter(position, letter)
+        })
+    }
+}
+
+fn map_letters<F: Fn(usize, u8) -> u8>(text: &str, f: F) -> String {
+    let mut position = 0;
+    text.chars()
+        .map(|c| {
+            let base = match c {
+                'a'..='z' => b'a',
+                'A'..='Z' => b'A',
+                _ => return c,
+            };
+            let letter = f(position, c as u8 - base);
+            position += 1;
+            (base + letter % 26) as char
+        })
+        .collect()
+}
+
+/// a decryption of a classical cipher, along with its key
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassicalSolution<C> {
+    pub cipher: C,
+    pub plaintext: String,
+    /// the higher, the more plausible
+    pub score: f64,
+}
+
+impl<C: LetterCipher> ClassicalSolution<C> {
+    fn new<F: Fn(&str) -> f64>(cipher: C, ciphertext: &str, scorer: F) -> Self {
+        let plaintext = cipher.decrypt(ciphertext);
+        Self {
+            score: scorer(&plaintext),
+            plaintext,
+            cipher,
+        }
+    }
+}
+
+/// the best rated decryption among the ones given by each cipher
+fn best_solution<C, I, F>(ciphers: I, ciphertext: &str, scorer: F) -> ClassicalSolution<C>
+where
+    C: LetterCipher,
+    I: IntoIterator<Item = C>,
+    F: Fn(&str) -> f64,
+{
+    ciphers
+        .into_iter()
+        .map(|cipher| ClassicalSolution::new(cipher, ciphertext, &scorer))
+        // on equal score, the first cipher is kept
+        .fold(
+            None,
+            |best: Option<ClassicalSolution<C>>, solution| match best {
+                Some(best) if best.score >= solution.score => Some(best),
+                _ => Some(solution),
+            },
+        )
+        .expect("there is at least one cipher to try")
+}
+
+/********************************** CAESAR ***********************************/
+
+/// shift every letter by the same amount
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Caesar {
+    shift: u8,
+}
+
+impl Caesar {
+    pub fn new(shift: u8) -> Self {
+        Self { shift: shift % 26 }
+    }
+
+    pub fn shift(&self) -> u8 {
+        self.shift
+    }
+}
+
+impl LetterCipher for Caesar {
+    fn encrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        (letter + self.shift) % 26
+    }
+
+    fn decrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        (letter + 26 - self.shift) % 26
+    }
+}
+
+/// try every shift, keeping the plaintext closest to the expected letter frequencies
+/// (e.g. `ByteFrequency::english` or `Dictionary::letter_frequency`)
+pub fn break_caesar(ciphertext: &str, frequency: &ByteFrequency) -> ClassicalSolution<Caesar> {
+    best_solution((0..26).map(Caesar::new), ciphertext, |plaintext| {
+        frequency.log_likelihood(&letters(plaintext))
+    })
+}
+
+/********************************* VIGENERE **********************************/
+
+/// shift the letters by the letters of a repeating key
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vigenere {
+    shifts: Vec<u8>,
+}
+
+impl Vigenere {
+    /// cipher of the letters of the key (other characters being ignored), or `None` if
+    /// there is none
+    pub fn new(key: &str) -> Option<Self> {
+        let shifts: Vec<u8> = letters(key).iter().map(|l| l - b'a').collect();
+        if shifts.is_empty() {
+            return None;
+        }
+        Some(Self { shifts })
+    }
+
+    /// the key, lowercase
+    pub fn key(&self) -> String {
+        self.shifts.iter().map(|s| (b'a' + s) as char).collect()
+    }
+}
+
+impl LetterCipher for Vigenere {
+    fn encrypt_letter(&self, position: usize, letter: u8) -> u8 {
+        Caesar::new(self.shifts[position % self.shifts.len()]).encrypt_letter(position, letter)
+    }
+
+    fn decrypt_letter(&self, position: usize, letter: u8) -> u8 {
+        Caesar::new(self.shifts[position % self.shifts.len()]).decrypt_letter(position, letter)
+    }
+}
+
+/// find the key length from the index of coincidence of the letters (up to
+/// `max_period`), then break each column of letters as a Caesar cipher against the
+/// expected letter frequencies; `None` if there are too few letters
+pub fn break_vigenere(
+    ciphertext: &str,
+    frequency: &ByteFrequency,
+    max_period: usize,
+) -> Option<ClassicalSolution<Vigenere>> {
+    let letters = letters(ciphertext);
+    let (period, _) = key_period(&letters, 1..=max_period)?;
+
+    let key: String = (0..period)
+        .map(|column| {
+            let column: String = letters
+                .iter()
+                .skip(column)
+                .step_by(period)
+                .map(|l| *l as char)
+                .collect();
+            (b'a' + break_caesar(&column, frequency).cipher.shift()) as char
+        })
+        .collect();
+
+    let cipher = Vigenere::new(&key).expect("the key holds a letter per column");
+    Some(ClassicalSolution::new(cipher, ciphertext, |plaintext| {
+        frequency.log_likelihood(&self::letters(plaintext))
+    }))
+}
+
+/********************************** AFFINE ***********************************/
+
+/// map each letter `x` to `a * x + b` modulo 26
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Affine {
+    a: u8,
+    b: u8,
+    a_inverse: u8,
+}
+
+impl Affine {
+    /// `None` if `a` is not coprime with 26, the cipher not being reversible
+    pub fn new(a: u8, b: u8) -> Option<Self> {
+        let a = a % 26;
+        Some(Self {
+            a,
+            b: b % 26,
+            a_inverse: inverse_mod_26(a)?,
+        })
+    }
+
+    pub fn key(&self) -> (u8, u8) {
+        (self.a, self.b)
+    }
+}
+
+impl LetterCipher for Affine {
+    fn encrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        ((self.a as u32 * letter as u32 + self.b as u32) % 26) as u8
+    }
+
+    fn decrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        ((self.a_inverse as u32 * (letter as u32 + 26 - self.b as u32)) % 26) as u8
+    }
+}
+
+/// try every key, keeping the plaintext closest to the expected letter frequencies
+pub fn break_affine(ciphertext: &str, frequency: &ByteFrequency) -> ClassicalSolution<Affine> {
+    let ciphers = (0..26).flat_map(|a| (0..26).filter_map(move |b| Affine::new(a, b)));
+    best_solution(ciphers, ciphertext, |plaintext| {
+        frequency.log_likelihood(&letters(plaintext))
+    })
+}
+
+/******************************* SUBSTITUTION ********************************/
+
+/// map each letter to another one, according to a permutation of the alphabet
+#[derive(Debug, PartialEq, Clone)]
+pub struct Substitution {
+    /// letter substituted to each letter
+    key: [u8; 26],
+    inverse: [u8; 26],
+}
+
+impl Substitution {
+    /// the key lists the letters substituted to 'a', 'b', ..., 'z' (either case), `None`
+    /// if it is not a permutation of the alphabet
+    pub fn new(key: &str) -> Option<Self> {
+        if key.len() != 26 || !key.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let mut permutation = [0u8; 26];
+        for (letter, substitute) in permutation.iter_mut().zip(key.bytes()) {
+            *letter = substitute.to_ascii_lowercase() - b'a';
+        }
+        Self::from_permutation(permutation)
+    }
+
+    fn from_permutation(key: [u8; 26]) -> Option<Self> {
+        let mut inverse = [26u8; 26];
+        for (letter, substitute) in key.iter().enumerate() {
+            inverse[*substitute as usize] = letter as u8;
+        }
+        if inverse.contains(&26) {
+            return None;
+        }
+        Some(Self { key, inverse })
+    }
+
+    /// the letters substituted to 'a', 'b', ..., 'z'
+    pub fn key(&self) -> String {
+        self.key.iter().map(|l| (b'a' + l) as char).collect()
+    }
+
+    /// the same substitution, the plaintext letters substituted to `first` & `second`
+    /// in the ciphertext being exchanged
+    fn swapped(&self, first: usize, second: usize) -> Self {
+        let mut inverse = self.inverse;
+        inverse.swap(first, second);
+        let mut key = [0u8; 26];
+        for (substitute, letter) in inverse.iter().enumerate() {
+            key[*letter as usize] = substitute as u8;
+        }
+        Self { key, inverse }
+    }
+}
+
+impl LetterCipher for Substitution {
+    fn encrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        self.key[letter as usize]
+    }
+
+    fn decrypt_letter(&self, _position: usize, letter: u8) -> u8 {
+        self.inverse[letter as usize]
+    }
+}
+
+/// hill climbing: starting from the key matching the letter frequencies of the
+/// ciphertext with the english ones (then from random keys), swap two letters of the
+/// key as long as it improves the n-gram score of the plaintext
+pub fn break_substitution(ciphertext: &str, model: &NgramModel) -> ClassicalSolution<Substitution> {
+    let scorer = |plaintext: &str| model.score(plaintext.as_bytes());
+
+    // ciphertext letters by decreasing frequency, mapped to the english ones
+    let mut counts = [0usize; 26];
+    for letter in letters(ciphertext) {
+        counts[(letter - b'a') as usize] += 1;
+    }
+    let mut order: Vec<usize> = (0..26).collect();
+    order.sort_by_key(|letter| std::cmp::Reverse(counts[*letter]));
+    let mut inverse = [0u8; 26];
+    for (rank, letter) in order.iter().enumerate() {
+        inverse[*letter] = ENGLISH_LETTER_ORDER[rank] - b'a';
+    }
+    let mut key = [0u8; 26];
+    for (substitute, letter) in inverse.iter().enumerate() {
+        key[*letter as usize] = substitute as u8;
+    }
+    let start = Substitution::from_permutation(key).expect("the key is a permutation");
+
+    let mut random = Xorshift::default();
+    let mut best: Option<ClassicalSolution<Substitution>> = None;
+    for restart in 0..RESTARTS {
+        let mut cipher = start.clone();
+        if restart > 0 {
+            for _ in 0..26 {
+                cipher = cipher.swapped(random.next(26), random.next(26));
+            }
+        }
+
+        let mut current = ClassicalSolution::new(cipher, ciphertext, scorer);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for first in 0..26 {
+                for second in first + 1..26 {
+                    let candidate = ClassicalSolution::new(
+                        current.cipher.swapped(first, second),
+                        ciphertext,
+                        scorer,
+                    );
+                    if candidate.score > current.score {
+                        current = candidate;
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        if best.as_ref().is_none_or(|best| current.score > best.score) {
+            best = Some(current);
+        }
+    }
+    best.expect("there is at least one restart")
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{
+        break_affine, break_caesar, break_substitution, break_vigenere, Affine, Caesar,
+        LetterCipher, Substitution, Vigenere,
+    };
+    use crate::structs::{ByteFrequency, Dictionary, NgramModel};
+
+    const PLAINTEXT: &str = "Cryptography is the practice and study of techniques for secure \
+        communication in the presence of adversarial behavior. More generally, it is about \
+        constructing and analyzing protocols that prevent third parties or the public from \
+        reading private messages. Modern cryptography exists at the intersection of the \
+        disciplines of mathematics, computer science, information security and electrical \
+        engineering. Before the modern era, cryptography was effectively synonymous with \
+        encryption, converting readable information from a plain state to unintelligible \
+        nonsense. The sender of an encrypted message shares the decryption technique only \
+        with the intended recipients to preclude access from adversaries.";
+
+    #[test]
+    fn caesar() {
+        let cipher = Caesar::new(29);
+        assert_eq!(cipher.shift(), 3);
+        assert_eq!(cipher.encrypt("Hello, World!"), "Khoor, Zruog!");
+        assert_eq!(cipher.decrypt("Khoor, Zruog!"), "Hello, World!");
+
+        let ciphertext = Caesar::new(11).encrypt(PLAINTEXT);
+        let solution = break_caesar(&ciphertext, &ByteFrequency::english());
+        assert_eq!(solution.cipher, Caesar::new(11));
+        assert_eq!(solution.plaintext, PLAINTEXT);
+    }
+
+    #[test]
+    fn vigenere() {
+        let cipher = Vigenere::new("Lemon!").unwrap();
+        assert_eq!(cipher.key(), "lemon");
+        assert_eq!(cipher.encrypt("Attack at dawn"), "Lxfopv ef rnhr");
+        assert_eq!(cipher.decrypt("Lxfopv ef rnhr"), "Attack at dawn");
+        assert_eq!(Vigenere::new("42"), None);
+
+        let ciphertext = Vigenere::new("cipher").unwrap().encrypt(PLAINTEXT);
+        let frequency = Dictionary::english().letter_frequency();
+        let solution = break_vigenere(&ciphertext, &frequency, 20).unwrap();
+        assert_eq!(solution.cipher.key(), "cipher");
+        assert_eq!(solution.plaintext, PLAINTEXT);
+        assert_eq!(break_vigenere("", &frequency, 20), None);
+    }
+
+    #[test]
+    fn affine() {
+        let cipher = Affine::new(5, 8).unwrap();
+        assert_eq!(cipher.encrypt("Affine cipher"), "Ihhwvc swfrcp");
+        assert_eq!(cipher.decrypt("Ihhwvc swfrcp"), "Affine cipher");
+        assert_eq!(Affine::new(13, 1), None);
+
+        let ciphertext = Affine::new(7, 3).unwrap().encrypt(PLAINTEXT);
+        let solution = break_affine(&ciphertext, &ByteFrequency::english());
+        assert_eq!(solution.cipher.key(), (7, 3));
+        assert_eq!(solution.plaintext, PLAINTEXT);
+    }
+
+    #[test]
+    fn substitution() {
+        let cipher = Substitution::new("QWERTYUIOPASDFGHJKLZXCVBNM").unwrap();
+        assert_eq!(cipher.key(), "qwertyuiopasdfghjklzxcvbnm");
+        assert_eq!(cipher.encrypt("Hello, World!"), "Itssg, Vgksr!");
+        assert_eq!(cipher.decrypt("Itssg, Vgksr!"), "Hello, World!");
+        assert_eq!(Substitution::new("qwertyuiopasdfghjklzxcvbnq"), None);
+        assert_eq!(Substitution::new("qwerty"), None);
+
+        let ciphertext = cipher.encrypt(PLAINTEXT);
+        let solution = break_substitution(&ciphertext, &NgramModel::english());
+
+        // rare letters may remain swapped, but the text is readable
+        let correct = solution
+            .plaintext
+            .chars()
+            .zip(PLAINTEXT.chars())
+            .filter(|(a, b)| a == b)
+            .count();
+        assert!(correct as f64 > 0.95 * PLAINTEXT.len() as f64);
+    }
+}
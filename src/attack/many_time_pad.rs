@@ -0,0 +1,283 @@
+use std::error::Error;
+use std::fmt;
+
+use super::scorer::Scorer;
+use crate::rank;
+use crate::structs::{is_printable_ascii, Hex};
+
+/// symbol displayed for the bytes of a plaintext whose keystream byte is unknown
+const UNKNOWN_SYMBOL: char = '_';
+/// symbol displayed for the bytes of a plaintext that are not printable
+const UNPRINTABLE_SYMBOL: char = '.';
+
+/// a position where a crib gives a plausible plaintext, see `ManyTimePad::drag`
+#[derive(Debug, PartialEq, Clone)]
+pub struct CribMatch {
+    /// indexes of the two ciphertexts, one of the plaintexts holding the crib
+    pub pair: (usize, usize),
+    /// position of the crib
+    pub offset: usize,
+    /// fragment of the other plaintext at the same position
+    pub revealed: Hex,
+    pub score: f64,
+}
+
+/// error raised when pinning keystream bytes that no ciphertext covers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PadError {
+    /// there is no ciphertext at this index
+    NoCiphertext(usize),
+    /// first offset past the end of the ciphertext (or of every ciphertext), which holds
+    /// `length` bytes
+    OutOfRange { offset: usize, length: usize },
+}
+
+impl fmt::Display for PadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoCiphertext(index) => write!(f, "no ciphertext at index {}", index),
+            Self::OutOfRange { offset, length } => {
+                write!(f, "offset {} is past the end of {} bytes", offset, length)
+            }
+        }
+    }
+}
+
+impl Error for PadError {}
+
+/********************************* MANY TIME PAD ******************************/
+
+/// several ciphertexts XORed with the same keystream, some bytes of which are pinned
+/// as they get guessed
+#[derive(Debug, PartialEq, Clone)]
+pub struct ManyTimePad {
+    ciphertexts: Vec<Hex>,
+    keystream: Vec<Option<u8>>,
+}
+
+impl ManyTimePad {
+    pub fn new(ciphertexts: Vec<Hex>) -> Self {
+        let length = ciphertexts.iter().map(|c| c.len()).max().unwrap_or(0);
+        Self {
+            ciphertexts,
+            keystream: vec![None; length],
+        }
+    }
+
+    pub fn ciphertexts(&self) -> &[Hex] {
+        &self.ciphertexts
+    }
+
+    /// bytes of the keystream pinned so far
+    pub fn keystream(&self) -> &[Option<u8>] {
+        &self.keystream
+    }
+
+    /// pin a byte of the keystream, failing if the offset is past every ciphertext
+    pub fn pin(&mut self, offset: usize, byte: u8) -> Result<(), PadError> {
+        let length = self.keystream.len();
+        let key = self
+            .keystream
+            .get_mut(offset)
+            .ok_or(PadError::OutOfRange { offset, length })?;
+        *key = Some(byte);
+        Ok(())
+    }
+
+    /// forget a byte of the keystream
+    pub fn unpin(&mut self, offset: usize) {
+        if let Some(key) = self.keystream.get_mut(offset) {
+            *key = None;
+        }
+    }
+
+    /// pin the keystream bytes revealed by a guessed fragment of the `index`-th
+    /// plaintext, starting at `offset`, failing if there is no such plaintext or if the
+    /// fragment exceeds it
+    pub fn pin_plaintext(
+        &mut self,
+        index: usize,
+        offset: usize,
+        plaintext: &[u8],
+    ) -> Result<(), PadError> {
+        let ciphertext = self
+            .ciphertexts
+            .get(index)
+            .ok_or(PadError::NoCiphertext(index))?
+            .as_bytes();
+        let length = ciphertext.len();
+        let fragment = offset
+            .checked_add(plaintext.len())
+            .and_then(|end| ciphertext.get(offset..end))
+            .ok_or(PadError::OutOfRange {
+                offset: offset.max(length),
+                length,
+            })?;
+
+        for (i, (byte, p)) in fragment.iter().zip(plaintext.iter()).enumerate() {
+            self.keystream[offset + i] = Some(byte ^ p);
+        }
+        Ok(())
+    }
+
+    /// bytes of each plaintext, `None` where the keystream is unknown
+    pub fn plaintexts(&self) -> Vec<Vec<Option<u8>>> {
+        self.ciphertexts
+            .iter()
+            .map(|ciphertext| {
+                ciphertext
+                    .as_bytes()
+                    .iter()
+                    .zip(self.keystream.iter())
+                    .map(|(byte, key)| key.map(|key| byte ^ key))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// each plaintext as a line of text, unknown bytes being displayed as `_` and
+    /// unprintable ones as `.`
+    pub fn reveal(&self) -> Vec<String> {
+        self.plaintexts()
+            .iter()
+            .map(|plaintext| {
+                plaintext
+                    .iter()
+                    .map(|byte| match byte {
+                        None => UNKNOWN_SYMBOL,
+                        Some(b) if is_printable_ascii(*b) => *b as char,
+                        Some(_) => UNPRINTABLE_SYMBOL,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// slide the crib across the XOR of every pair of ciphertexts (which is the XOR of
+    /// both plaintexts), and keep the `top_n` positions where the other plaintext is
+    /// accepted by the scorer, ranked by decreasing score
+    pub fn drag<S: Scorer + ?Sized>(
+        &self,
+        crib: &[u8],
+        scorer: &S,
+        top_n: usize,
+    ) -> Vec<CribMatch> {
+        let mut matches = Vec::new();
+        if crib.is_empty() {
+            return matches;
+        }
+
+        for (i, first) in self.ciphertexts.iter().enumerate() {
+            for (j, second) in self.ciphertexts.iter().enumerate().skip(i + 1) {
+                let length = first.len().min(second.len());
+                let xored = Hex::new(&first[..length])
+                    .xor(&Hex::new(&second[..length]))
+                    .expect("both fragments have the same length");
+
+                for offset in 0..(length + 1).saturating_sub(crib.len()) {
+                    let revealed: Vec<u8> = xored.as_bytes()[offset..offset + crib.len()]
+                        .iter()
+                        .zip(crib.iter())
+                        .map(|(byte, c)| byte ^ c)
+                        .collect();
+                    if !scorer.accepts(&revealed) {
+                        continue;
+                    }
+
+                    matches.push(CribMatch {
+                        pair: (i, j),
+                        offset,
+                        score: scorer.score(&revealed),
+                        revealed: Hex::from(revealed),
+                    });
+                }
+            }
+        }
+
+        rank::descending(&mut matches, |m| m.score);
+        matches.truncate(top_n);
+        matches
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{ManyTimePad, PadError};
+    use crate::attack::scorer::{DictionaryScorer, RejectIf};
+    use crate::random;
+    use crate::structs::{Dictionary, Hex};
+
+    const PLAINTEXTS: [&str; 3] = [
+        "we hold these truths to be self-evident",
+        "four score and seven years ago our fathers",
+        "it was the best of times, it was the worst",
+    ];
+
+    fn pad() -> ManyTimePad {
+        let keystream = Hex::from(random::bytes(64));
+        ManyTimePad::new(
+            PLAINTEXTS
+                .iter()
+                .map(|p| {
+                    Hex::encode(p)
+                        .xor(&Hex::new(&keystream[..p.len()]))
+                        .unwrap()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn drag_crib() {
+        let pad = pad();
+        let dictionary = Dictionary::english();
+        let scorer = RejectIf::unprintable(DictionaryScorer::new(&dictionary));
+
+        let matches = pad.drag(b" the ", &scorer, 10);
+        assert!(!matches.is_empty());
+        assert!(matches.windows(2).all(|m| m[0].score >= m[1].score));
+        // " the " at offset 6 of the third plaintext reveals "d the" in the first one
+        assert!(matches
+            .iter()
+            .any(|m| m.pair == (0, 2) && m.offset == 6 && m.revealed == Hex::encode("d the")));
+    }
+
+    #[test]
+    fn pin_keystream() {
+        let mut pad = pad();
+        assert_eq!(pad.reveal()[0], "_".repeat(39));
+
+        pad.pin_plaintext(0, 3, b"hold").unwrap();
+        assert_eq!(&pad.reveal()[1][..8], "___r sc_");
+        assert_eq!(&pad.reveal()[2][..8], "___was _");
+
+        let byte = pad.ciphertexts()[0][0].decode() ^ b'w';
+        pad.pin(0, byte).unwrap();
+        assert_eq!(&pad.reveal()[0][..8], "w__hold_");
+        assert_eq!(pad.plaintexts()[1][0], Some(b'f'));
+
+        pad.unpin(0);
+        assert_eq!(pad.keystream()[0], None);
+
+        assert_eq!(
+            pad.pin_plaintext(0, 37, b"nt!"),
+            Err(PadError::OutOfRange {
+                offset: 39,
+                length: 39
+            })
+        );
+        assert_eq!(
+            pad.pin_plaintext(3, 0, b"four"),
+            Err(PadError::NoCiphertext(3))
+        );
+        assert_eq!(
+            pad.pin(42, 0),
+            Err(PadError::OutOfRange {
+                offset: 42,
+                length: 42
+            })
+        );
+    }
+}
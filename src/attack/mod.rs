@@ -1 +1,5 @@
+pub mod classical;
+pub mod many_time_pad;
+pub mod repeating_xor;
+pub mod scorer;
 pub mod xor_cipher;
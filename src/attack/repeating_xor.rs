@@ -0,0 +1,305 @@
+use std::ops::RangeInclusive;
+
+use super::scorer::Scorer;
+use super::xor_cipher::single_byte_xor;
+use crate::rank;
+use crate::structs::{Hex, HexSymbol};
+
+/// maximum number of pairs of blocks compared for a given keysize
+const MAX_PAIRS: usize = 256;
+
+/// a possible size of a repeating XOR key
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct KeysizeCandidate {
+    pub keysize: usize,
+    /// mean hamming distance between blocks, in bits per byte: the lower, the more
+    /// likely the keysize
+    pub distance: f64,
+}
+
+/// mean hamming distance between pairs of blocks of the input (up to `MAX_PAIRS` pairs,
+/// each block with the following ones), normalized by the keysize, or `None` if there
+/// are less than two blocks
+fn normalized_distance(input: &Hex, keysize: usize) -> Option<f64> {
+    let blocks: Vec<Hex> = input.chunks_exact(keysize).map(Hex::new).collect();
+    if blocks.len() < 2 {
+        return None;
+    }
+
+    let pairs: Vec<(usize, usize)> = (1..blocks.len())
+        .flat_map(|gap| (0..blocks.len() - gap).map(move |i| (i, i + gap)))
+        .take(MAX_PAIRS)
+        .collect();
+    let total: u32 = pairs
+        .iter()
+        .map(|(i, j)| blocks[*i].hamming(&blocks[*j]))
+        .sum();
+    Some(total as f64 / pairs.len() as f64 / keysize as f64)
+}
+
+/// rank the keysizes most likely used to encrypt the input with a repeating XOR, by
+/// increasing normalized hamming distance between its blocks, keeping the `top_n` best
+///
+/// keysizes leaving less than two full blocks in the input are skipped
+pub fn estimate_keysize(
+    input: &Hex,
+    keysizes: RangeInclusive<usize>,
+    top_n: usize,
+) -> Vec<KeysizeCandidate> {
+    let mut candidates: Vec<KeysizeCandidate> = keysizes
+        .filter(|keysize| *keysize > 0)
+        .filter_map(|keysize| {
+            normalized_distance(input, keysize)
+                .map(|distance| KeysizeCandidate { keysize, distance })
+        })
+        .collect();
+
+    rank::ascending(&mut candidates, |candidate| candidate.distance);
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// a decryption of a repeating XOR ciphertext, see `break_repeating_xor`
+#[derive(Debug, PartialEq, Clone)]
+pub struct RepeatingXorSolution {
+    pub key: Hex,
+    pub plaintext: Hex,
+    /// score of the whole plaintext
+    pub score: f64,
+    /// margin between the scores of the best & second best byte of each column (i.e.
+    /// of each byte of the key): the higher, the more reliable the byte
+    pub confidence: Vec<f64>,
+}
+
+/// smallest pattern the key is a repetition of, e.g. "ICE" for "ICEICE"
+fn shortest_key(key: &[HexSymbol]) -> &[HexSymbol] {
+    let period = (1..key.len())
+        .filter(|period| key.len().is_multiple_of(*period))
+        .find(|period| key.chunks(*period).all(|chunk| chunk == &key[..*period]))
+        .unwrap_or(key.len());
+    &key[..period]
+}
+
+/// break each column of the input (the bytes encrypted with the same key byte), giving
+/// the key & confidence of each byte, or `None` if the scorer rejects every candidate
+/// of a column
+fn solve_columns<S: Scorer + ?Sized>(
+    input: &Hex,
+    scorer: &S,
+    keysize: usize,
+) -> Option<(Vec<HexSymbol>, Vec<f64>)> {
+    let mut key = Vec::with_capacity(keysize);
+    let mut confidence = Vec::with_capacity(keysize);
+    for column in input.transpose(keysize) {
+        let candidates = single_byte_xor(&column, scorer, 2);
+
+        let best = candidates.first()?;
+        key.push(best.key);
+        confidence.push(
+            candidates
+                .get(1)
+                .map_or(f64::INFINITY, |second| best.score - second.score),
+        );
+    }
+    Some((key, confidence))
+}
+
+/// break a repeating XOR ciphertext: try the `tries` most likely keysizes (see
+/// `estimate_keysize`), solve each column with a single-byte XOR attack, and keep the
+/// plaintext best rated by the scorer
+///
+/// keys repeating a shorter pattern are reduced to it, so the key is the same whether a
+/// keysize or one of its multiples is tried
+pub fn break_repeating_xor<S: Scorer + ?Sized>(
+    input: &Hex,
+    scorer: &S,
+    keysizes: RangeInclusive<usize>,
+    tries: usize,
+) -> Option<RepeatingXorSolution> {
+    let mut best: Option<RepeatingXorSolution> = None;
+    for candidate in estimate_keysize(input, keysizes, tries) {
+        let (key, confidence) = match solve_columns(input, scorer, candidate.keysize) {
+            Some(solution) => solution,
+            None => continue,
+        };
+        let key = shortest_key(&key);
+        let confidence = confidence[..key.len()].to_vec();
+        let key = Hex::new(key);
+
+        let plaintext = input.rolling_xor(&key);
+        let score = scorer.score(plaintext.as_bytes());
+        if best.as_ref().is_none_or(|best| score > best.score) {
+            best = Some(RepeatingXorSolution {
+                key,
+                plaintext,
+                score,
+                confidence,
+            });
+        }
+    }
+    best
+}
+
+/// smallest period among `keysizes` of a partially known keystream, along with the key
+/// it repeats: each byte of the key must be known at least once, and all its known
+/// occurrences must agree
+///
+/// only the known bytes are visited, for each keysize no longer than their number
+fn smallest_period(keystream: &[Option<u8>], keysizes: RangeInclusive<usize>) -> Option<Vec<u8>> {
+    let known: Vec<(usize, u8)> = keystream
+        .iter()
+        .enumerate()
+        .filter_map(|(i, byte)| byte.map(|byte| (i, byte)))
+        .collect();
+
+    keysizes
+        .filter(|period| *period > 0 && *period <= known.len())
+        .find_map(|period| {
+            let mut key: Vec<Option<u8>> = vec![None; period];
+            for (i, byte) in known.iter() {
+                match key[i % period] {
+                    Some(previous) if previous != *byte => return None,
+                    _ => key[i % period] = Some(*byte),
+                }
+            }
+            key.into_iter().collect()
+        })
+}
+
+/// recover the key of a repeating XOR from fragments of known plaintext (e.g. magic
+/// numbers or protocol headers), given with their offset in the plaintext
+///
+/// the key is the shortest one among `keysizes` consistent with the fragments, which
+/// must then cover each of its bytes at least once; returns `None` if the fragments
+/// overflow the ciphertext, contradict each other, or do not determine a whole key
+pub fn recover_key(
+    ciphertext: &Hex,
+    keysizes: RangeInclusive<usize>,
+    known: &[(usize, &[u8])],
+) -> Option<Hex> {
+    let mut keystream: Vec<Option<u8>> = vec![None; ciphertext.len()];
+    for (offset, fragment) in known.iter() {
+        let end = offset.checked_add(fragment.len())?;
+        let ciphertext = ciphertext.as_bytes().get(*offset..end)?;
+        for (i, (c, p)) in ciphertext.iter().zip(fragment.iter()).enumerate() {
+            let byte = c ^ p;
+            match keystream[offset + i] {
+                Some(known) if known != byte => return None,
+                _ => keystream[offset + i] = Some(byte),
+            }
+        }
+    }
+
+    smallest_period(&keystream, keysizes).map(Hex::from)
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{break_repeating_xor, estimate_keysize, recover_key, shortest_key};
+    use crate::attack::scorer::{FrequencyScorer, RejectIf};
+    use crate::structs::{Hex, HexSymbol};
+
+    const PLAINTEXT: &str = include_str!("../../data/corpus/english.txt");
+
+    #[test]
+    fn keysize_estimation() {
+        for key in ["ICE", "YELLOW SUBMARINE", "Terminator X: Bring the noise"].iter() {
+            let ciphertext = Hex::encode(&PLAINTEXT[..2000]).rolling_xor(&Hex::encode(key));
+            let candidates = estimate_keysize(&ciphertext, 2..=40, 3);
+
+            assert_eq!(candidates.len(), 3);
+            // multiples of the keysize are as likely as the keysize itself
+            assert_eq!(candidates[0].keysize % key.len(), 0, "{:?}", candidates);
+            assert!(candidates[0].distance <= candidates[1].distance);
+        }
+    }
+
+    #[test]
+    fn short_inputs() {
+        let ciphertext = Hex::encode("0123456789");
+        let candidates = estimate_keysize(&ciphertext, 0..=10, 10);
+
+        // keysizes 0 and 6 to 10 leave less than two blocks
+        assert_eq!(candidates.len(), 5);
+        assert!(candidates.iter().all(|c| (1..=5).contains(&c.keysize)));
+    }
+
+    #[test]
+    fn shortest_keys() {
+        let key = Hex::encode("ICEICE");
+        assert_eq!(shortest_key(&key), &Hex::encode("ICE")[..]);
+        let key = Hex::encode("ICEIC");
+        assert_eq!(shortest_key(&key), &key[..]);
+        assert_eq!(shortest_key(&[]), &[] as &[HexSymbol]);
+    }
+
+    #[test]
+    fn break_ciphertext() {
+        let scorer = FrequencyScorer::default();
+        for key in ["ICE", "YELLOW SUBMARINE", "Terminator X: Bring the noise"].iter() {
+            let plaintext = Hex::encode(&PLAINTEXT[..2000]);
+            let ciphertext = plaintext.rolling_xor(&Hex::encode(key));
+            let solution = break_repeating_xor(&ciphertext, &scorer, 2..=40, 3).unwrap();
+
+            assert_eq!(solution.key, Hex::encode(key));
+            assert_eq!(solution.plaintext, plaintext);
+            assert_eq!(solution.confidence.len(), key.len());
+            assert!(solution.confidence.iter().all(|c| *c > 0.0));
+        }
+    }
+
+    #[test]
+    fn rejected_columns() {
+        let ciphertext = Hex::encode(&PLAINTEXT[..200]).rolling_xor(&Hex::encode("ICE"));
+        let scorer = RejectIf::new(FrequencyScorer::default(), |_: &[u8]| true);
+        assert_eq!(break_repeating_xor(&ciphertext, &scorer, 2..=10, 3), None);
+    }
+
+    #[test]
+    fn known_plaintext() {
+        let plaintext = [
+            &b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"[..],
+            PLAINTEXT.as_bytes(),
+        ]
+        .concat();
+        let key = Hex::encode("secret");
+        let ciphertext = Hex::from(plaintext.clone()).rolling_xor(&key);
+
+        // the png signature covers the whole key
+        let recovered = recover_key(&ciphertext, 1..=40, &[(0, b"\x89PNG\r\n\x1a\n")]).unwrap();
+        assert_eq!(recovered, key);
+        assert_eq!(ciphertext.rolling_xor(&recovered), Hex::from(plaintext));
+
+        // so do scattered fragments
+        assert_eq!(
+            recover_key(&ciphertext, 1..=40, &[(0, b"\x89PN"), (9, b"\x00\x00\x0d")]),
+            Some(key)
+        );
+
+        // keysizes out of the range are not tried
+        let signature: &[u8] = b"\x89PNG\r\n\x1a\n";
+        assert_eq!(recover_key(&ciphertext, 2..=5, &[(0, signature)]), None);
+    }
+
+    #[test]
+    fn unknown_key() {
+        let ciphertext =
+            Hex::encode("Terminator X: Bring the noise").rolling_xor(&Hex::encode("ICE"));
+
+        // contradicting fragments
+        assert_eq!(
+            recover_key(&ciphertext, 1..=40, &[(0, b"Term"), (2, b"xx")]),
+            None
+        );
+        // overflowing fragment
+        assert_eq!(recover_key(&ciphertext, 1..=40, &[(27, b"se!")]), None);
+        assert_eq!(recover_key(&ciphertext, 1..=40, &[]), None);
+        // a period of 1 is contradicted, and longer ones are not covered
+        assert_eq!(
+            recover_key(&ciphertext, 1..=40, &[(0, b"T"), (2, b"r")]),
+            None
+        );
+    }
+}
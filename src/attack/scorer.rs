@@ -0,0 +1,295 @@
+use crate::structs::{is_text, ByteFrequency, Dictionary, NgramModel};
+
+/********************************** SCORER ***********************************/
+
+/// estimate how plausible a candidate plaintext is
+pub trait Scorer {
+    /// name of the scorer in score breakdowns
+    fn name(&self) -> &'static str;
+
+    /// the higher, the more plausible
+    fn score(&self, input: &[u8]) -> f64;
+
+    /// whether the input can be a plaintext at all
+    fn accepts(&self, _input: &[u8]) -> bool {
+        true
+    }
+
+    /// score given by each scorer involved, by name, summing to `score`
+    fn breakdown(&self, input: &[u8]) -> Vec<(&'static str, f64)> {
+        vec![(self.name(), self.score(input))]
+    }
+}
+
+impl<S: Scorer + ?Sized> Scorer for &S {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        (**self).score(input)
+    }
+
+    fn accepts(&self, input: &[u8]) -> bool {
+        (**self).accepts(input)
+    }
+
+    fn breakdown(&self, input: &[u8]) -> Vec<(&'static str, f64)> {
+        (**self).breakdown(input)
+    }
+}
+
+impl<S: Scorer + ?Sized> Scorer for Box<S> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        (**self).score(input)
+    }
+
+    fn accepts(&self, input: &[u8]) -> bool {
+        (**self).accepts(input)
+    }
+
+    fn breakdown(&self, input: &[u8]) -> Vec<(&'static str, f64)> {
+        (**self).breakdown(input)
+    }
+}
+
+/***************************** IMPLEMENTATIONS ********************************/
+
+/// mean log-likelihood of the bytes given expected frequencies (english by default)
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyScorer {
+    frequency: ByteFrequency,
+}
+
+impl FrequencyScorer {
+    pub fn new(frequency: ByteFrequency) -> Self {
+        Self { frequency }
+    }
+}
+
+impl Scorer for FrequencyScorer {
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        self.frequency.log_likelihood(input)
+    }
+}
+
+/// number of dictionary words found every 10 bytes
+pub struct DictionaryScorer<'a> {
+    dictionary: &'a Dictionary,
+}
+
+impl<'a> DictionaryScorer<'a> {
+    pub fn new(dictionary: &'a Dictionary) -> Self {
+        Self { dictionary }
+    }
+}
+
+impl Scorer for DictionaryScorer<'_> {
+    fn name(&self) -> &'static str {
+        "dictionary"
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        if input.is_empty() {
+            return 0.0;
+        }
+        let hits = self.dictionary.hits(&String::from_utf8_lossy(input));
+        10.0 * hits as f64 / input.len() as f64
+    }
+}
+
+/// mean log-probability of the n-grams of the input given a language model (english
+/// quadgrams by default), discriminating even short inputs
+#[derive(Debug, Clone, Default)]
+pub struct NgramScorer {
+    model: NgramModel,
+}
+
+impl NgramScorer {
+    pub fn new(model: NgramModel) -> Self {
+        Self { model }
+    }
+}
+
+impl Scorer for NgramScorer {
+    fn name(&self) -> &'static str {
+        "ngrams"
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        self.model.score(input)
+    }
+}
+
+/// proportion of printable ascii bytes (whitespace included)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrintableScorer;
+
+impl Scorer for PrintableScorer {
+    fn name(&self) -> &'static str {
+        "printable"
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        if input.is_empty() {
+            return 0.0;
+        }
+        input.iter().filter(|b| is_text(**b)).count() as f64 / input.len() as f64
+    }
+}
+
+/******************************* COMBINATORS **********************************/
+
+/// weighted sum of several scorers, accepting an input only if all of them do
+#[derive(Default)]
+pub struct WeightedSum<'a> {
+    components: Vec<(f64, Box<dyn Scorer + 'a>)>,
+}
+
+impl<'a> WeightedSum<'a> {
+    pub fn new() -> Self {
+        Self {
+            components: Vec::new(),
+        }
+    }
+
+    pub fn add<S: Scorer + 'a>(mut self, weight: f64, scorer: S) -> Self {
+        self.components.push((weight, Box::new(scorer)));
+        self
+    }
+}
+
+impl Scorer for WeightedSum<'_> {
+    fn name(&self) -> &'static str {
+        "weighted_sum"
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        self.components
+            .iter()
+            .map(|(weight, scorer)| weight * scorer.score(input))
+            .sum()
+    }
+
+    fn accepts(&self, input: &[u8]) -> bool {
+        self.components
+            .iter()
+            .all(|(_, scorer)| scorer.accepts(input))
+    }
+
+    fn breakdown(&self, input: &[u8]) -> Vec<(&'static str, f64)> {
+        self.components
+            .iter()
+            .flat_map(|(weight, scorer)| {
+                scorer
+                    .breakdown(input)
+                    .into_iter()
+                    .map(move |(name, score)| (name, weight * score))
+            })
+            .collect()
+    }
+}
+
+/// a scorer rejecting the inputs matching a predicate
+pub struct RejectIf<S, F> {
+    scorer: S,
+    predicate: F,
+}
+
+impl<S: Scorer, F: Fn(&[u8]) -> bool> RejectIf<S, F> {
+    pub fn new(scorer: S, predicate: F) -> Self {
+        Self { scorer, predicate }
+    }
+}
+
+impl<S: Scorer> RejectIf<S, fn(&[u8]) -> bool> {
+    /// reject inputs holding a byte that is neither printable ascii nor whitespace
+    pub fn unprintable(scorer: S) -> Self {
+        Self::new(scorer, |input| !input.iter().all(|b| is_text(*b)))
+    }
+}
+
+impl<S: Scorer, F: Fn(&[u8]) -> bool> Scorer for RejectIf<S, F> {
+    fn name(&self) -> &'static str {
+        self.scorer.name()
+    }
+
+    fn score(&self, input: &[u8]) -> f64 {
+        self.scorer.score(input)
+    }
+
+    fn accepts(&self, input: &[u8]) -> bool {
+        !(self.predicate)(input) && self.scorer.accepts(input)
+    }
+
+    fn breakdown(&self, input: &[u8]) -> Vec<(&'static str, f64)> {
+        self.scorer.breakdown(input)
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{FrequencyScorer, NgramScorer, PrintableScorer, RejectIf, Scorer, WeightedSum};
+    use crate::testing::{NOISE, TEXT};
+
+    #[test]
+    fn english_beats_noise() {
+        let scorers: Vec<Box<dyn Scorer>> = vec![
+            Box::new(FrequencyScorer::default()),
+            Box::new(NgramScorer::default()),
+        ];
+        for scorer in scorers.iter() {
+            assert!(
+                scorer.score(TEXT) > scorer.score(NOISE),
+                "{}",
+                scorer.name()
+            );
+        }
+    }
+
+    #[test]
+    fn printable_ratio() {
+        assert_eq!(PrintableScorer.score(b"ab\n\x00"), 0.75);
+        assert_eq!(PrintableScorer.score(b""), 0.0);
+    }
+
+    #[test]
+    fn weighted_sum() {
+        let scorer = WeightedSum::new()
+            .add(2.0, PrintableScorer)
+            .add(1.0, NgramScorer::default());
+
+        let breakdown = scorer.breakdown(TEXT);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0], ("printable", 2.0));
+        let total: f64 = breakdown.iter().map(|(_, score)| score).sum();
+        assert!((scorer.score(TEXT) - total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reject_if() {
+        let scorer = RejectIf::unprintable(PrintableScorer);
+        assert!(scorer.accepts(b"hello world\n"));
+        assert!(!scorer.accepts(b"hello\x00world"));
+
+        let scorer = RejectIf::new(NgramScorer::default(), |input: &[u8]| input.len() < 4);
+        assert!(!scorer.accepts(b"the"));
+
+        // rejection propagates through combinators
+        let scorer = WeightedSum::new()
+            .add(1.0, scorer)
+            .add(1.0, PrintableScorer);
+        assert!(!scorer.accepts(b"the"));
+        assert!(scorer.accepts(b"then"));
+    }
+}
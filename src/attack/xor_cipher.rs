@@ -1,55 +1,358 @@
-use crate::structs::{Dictionary, Hex, HexSymbol};
+use std::thread;
 
-use std::u8;
+use super::scorer::Scorer;
+use crate::rank;
+use crate::structs::{ByteFrequency, Dictionary, Hex, HexSymbol};
 
-pub fn single_byte_xor_score_recognition(dictionary: &mut Dictionary, input: Hex) {
-    let mut max_score: u32 = 1;
-    let mut best_fit: String = String::new();
-    let mut key: HexSymbol = HexSymbol::new(0);
-
-    for byte in u8::MIN..=u8::MAX {
-        let hs = HexSymbol::new(byte);
+/// a possible decryption of a single-byte XOR ciphertext
+#[derive(Debug, PartialEq, Clone)]
+pub struct Candidate {
+    pub key: HexSymbol,
+    pub plaintext: Hex,
+    /// overall score, the higher the more plausible
+    pub score: f64,
+    /// score given by each scorer involved, by name
+    pub breakdown: Vec<(&'static str, f64)>,
+}
 
-        let decoded = input.rolling_xor(&Hex::new(hs));
-        let decoded_str = decoded.decode();
+/// a possible decryption of one of several ciphertexts, see `detect_single_byte_xor`
+#[derive(Debug, PartialEq, Clone)]
+pub struct LineCandidate {
+    /// index of the ciphertext among the inputs
+    pub line: usize,
+    pub candidate: Candidate,
+}
 
-        if decoded.iter().fold(true, |acc, elem| {
-            acc && elem.is_printable() && !elem.is_extended()
-        }) {
-            let decoded_score = dictionary.letter_score(&decoded_str);
-            if decoded_score >= max_score {
-                max_score = decoded_score;
-                best_fit = decoded_str;
-                key = HexSymbol::new(byte);
+/// decrypt the input with every possible key byte and keep the `top_n` best candidates
+/// accepted by the scorer, ranked by decreasing score
+pub fn single_byte_xor<S: Scorer + ?Sized>(
+    input: &Hex,
+    scorer: &S,
+    top_n: usize,
+) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = (u8::MIN..=u8::MAX)
+        .filter_map(|byte| {
+            let key = HexSymbol::new(byte);
+            let plaintext = input.rolling_xor(&Hex::new(&[key]));
+            if !scorer.accepts(plaintext.as_bytes()) {
+                return None;
             }
+
+            let breakdown = scorer.breakdown(plaintext.as_bytes());
+            Some(Candidate {
+                key,
+                score: breakdown.iter().map(|(_, score)| score).sum(),
+                plaintext,
+                breakdown,
+            })
+        })
+        .collect();
+
+    rank::descending(&mut candidates, |candidate| candidate.score);
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// find which of the inputs was encrypted with a single-byte XOR: break each of them
+/// and keep the `top_n` best candidates overall, ranked by decreasing score (the
+/// first one being the most likely line, key & plaintext)
+pub fn detect_single_byte_xor<S: Scorer + ?Sized>(
+    inputs: &[Hex],
+    scorer: &S,
+    top_n: usize,
+) -> Vec<LineCandidate> {
+    let candidates = inputs
+        .iter()
+        .enumerate()
+        .flat_map(|(line, input)| {
+            single_byte_xor(input, scorer, top_n)
+                .into_iter()
+                .map(move |candidate| LineCandidate { line, candidate })
+        })
+        .collect();
+    rank_lines(candidates, top_n)
+}
+
+/// same as `detect_single_byte_xor`, the inputs being shared between `threads` threads
+pub fn detect_single_byte_xor_parallel<S: Scorer + Sync + ?Sized>(
+    inputs: &[Hex],
+    scorer: &S,
+    top_n: usize,
+    threads: usize,
+) -> Vec<LineCandidate> {
+    if inputs.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = inputs.len().div_ceil(threads.max(1));
+
+    let candidates = thread::scope(|scope| {
+        let handles: Vec<_> = inputs
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                scope.spawn(move || {
+                    let mut candidates = detect_single_byte_xor(chunk, scorer, top_n);
+                    for candidate in candidates.iter_mut() {
+                        candidate.line += i * chunk_size;
+                    }
+                    candidates
+                })
+            })
+            .collect();
+
+        // joined in order, so that the ranking is the same as the sequential one
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("a detection thread panicked"))
+            .collect()
+    });
+    rank_lines(candidates, top_n)
+}
+
+/// rank the candidates by decreasing score, the candidates being given in line order so
+/// that on equal score the first line comes first
+fn rank_lines(mut candidates: Vec<LineCandidate>, top_n: usize) -> Vec<LineCandidate> {
+    rank::descending(&mut candidates, |candidate| candidate.candidate.score);
+    candidates.truncate(top_n);
+    candidates
+}
+
+/******************************** DEPRECATED *********************************/
+
+/// decrypt the input with every possible key byte and keep the `top_n` best candidates,
+/// ranked by decreasing score
+///
+/// the scorer returns the score breakdown of a plaintext, or `None` to reject it; the
+/// overall score is the sum of the breakdown
+#[deprecated(note = "use `single_byte_xor` with a `Scorer`")]
+pub fn single_byte_xor_candidates<F>(input: &Hex, top_n: usize, mut scorer: F) -> Vec<Candidate>
+where
+    F: FnMut(&Hex) -> Option<Vec<(&'static str, f64)>>,
+{
+    let mut candidates: Vec<Candidate> = (u8::MIN..=u8::MAX)
+        .filter_map(|byte| {
+            let key = HexSymbol::new(byte);
+            let plaintext = input.rolling_xor(&Hex::new(&[key]));
+
+            scorer(&plaintext).map(|breakdown| Candidate {
+                key,
+                score: breakdown.iter().map(|(_, score)| score).sum(),
+                plaintext,
+                breakdown,
+            })
+        })
+        .collect();
+
+    rank::descending(&mut candidates, |candidate| candidate.score);
+    candidates.truncate(top_n);
+    candidates
+}
+
+fn is_printable_ascii(plaintext: &Hex) -> bool {
+    plaintext
+        .iter()
+        .all(|elem| elem.is_printable() && !elem.is_extended())
+}
+
+/// rank the printable decryptions by the letter frequencies of the dictionary
+#[deprecated(note = "use `single_byte_xor` with a `Scorer`")]
+#[allow(deprecated)]
+pub fn single_byte_xor_score_recognition(
+    dictionary: &mut Dictionary,
+    input: &Hex,
+    top_n: usize,
+) -> Vec<Candidate> {
+    single_byte_xor_candidates(input, top_n, |plaintext| {
+        if !is_printable_ascii(plaintext) {
+            return None;
+        }
+
+        let letter_score = dictionary.letter_score(&plaintext.decode_lossy());
+        Some(vec![("letter_score", letter_score as f64)])
+    })
+}
+
+/// rank the printable decryptions holding enough dictionary words (one every 10 bytes)
+/// by their number of words
+#[deprecated(note = "use `single_byte_xor` with a `DictionaryScorer`")]
+#[allow(deprecated)]
+pub fn single_byte_xor_word_recognition(
+    dictionary: &Dictionary,
+    input: &Hex,
+    top_n: usize,
+) -> Vec<Candidate> {
+    single_byte_xor_candidates(input, top_n, |plaintext| {
+        if !is_printable_ascii(plaintext) {
+            return None;
+        }
+
+        let hits = dictionary.hits(&plaintext.decode_lossy());
+        if hits > (plaintext.len() / 10) as u32 {
+            Some(vec![("dictionary_hits", hits as f64)])
+        } else {
+            None
         }
+    })
+}
+
+/// rank the decryptions by the likelihood of their bytes given the expected frequencies
+/// (e.g. `ByteFrequency::english()`), without any external file
+#[deprecated(note = "use `single_byte_xor` with a `FrequencyScorer`")]
+#[allow(deprecated)]
+pub fn single_byte_xor_frequency_recognition(
+    frequency: &ByteFrequency,
+    input: &Hex,
+    top_n: usize,
+) -> Vec<Candidate> {
+    single_byte_xor_candidates(input, top_n, |plaintext| {
+        Some(vec![(
+            "log_likelihood",
+            frequency.log_likelihood(plaintext.as_bytes()),
+        )])
+    })
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    #[allow(deprecated)]
+    use super::{
+        detect_single_byte_xor, detect_single_byte_xor_parallel, single_byte_xor,
+        single_byte_xor_candidates, single_byte_xor_frequency_recognition,
+    };
+    use crate::attack::scorer::{FrequencyScorer, RejectIf, Scorer};
+    use crate::structs::{ByteFrequency, Hex, HexSymbol};
+    use std::convert::TryFrom;
+    use std::fs;
+
+    /// score plaintexts by their number of 'a'
+    struct CountA;
+
+    impl Scorer for CountA {
+        fn name(&self) -> &'static str {
+            "a_count"
+        }
+
+        fn score(&self, input: &[u8]) -> f64 {
+            input.iter().filter(|b| **b == b'a').count() as f64
+        }
+    }
+
+    /// score plaintexts by their number of 'a' divided by zero
+    struct Undefined;
+
+    impl Scorer for Undefined {
+        fn name(&self) -> &'static str {
+            "undefined"
+        }
+
+        fn score(&self, input: &[u8]) -> f64 {
+            CountA.score(input) / 0.0
+        }
+    }
+
+    #[test]
+    fn ranked_candidates() {
+        let input = Hex::encode("aaab").rolling_xor(&Hex::new(&[HexSymbol::new(42)]));
+        let candidates = single_byte_xor(&input, &CountA, 3);
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].key, HexSymbol::new(42));
+        assert_eq!(candidates[0].plaintext, Hex::encode("aaab"));
+        assert_eq!(candidates[0].score, 3.0);
+        assert_eq!(candidates[0].breakdown, vec![("a_count", 3.0)]);
+        assert!(candidates[1].score <= candidates[0].score);
+        assert!(candidates[2].score <= candidates[1].score);
+    }
+
+    #[test]
+    fn rejected_candidates() {
+        let input = Hex::encode("abc");
+        let scorer = RejectIf::new(CountA, |plaintext: &[u8]| plaintext != b"abc");
+        let candidates = single_byte_xor(&input, &scorer, 256);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].key, HexSymbol::new(0));
+    }
+
+    #[test]
+    fn undefined_scores() {
+        // plaintexts without any 'a' get a NaN score
+        let input = Hex::encode("abc");
+        let candidates = single_byte_xor(&input, &Undefined, 256);
+
+        assert_eq!(candidates.len(), 256);
+        // the NaN scores rank last
+        let first_nan = candidates.iter().position(|c| c.score.is_nan()).unwrap();
+        assert_eq!(candidates[0].score, f64::INFINITY);
+        assert!(candidates[first_nan..].iter().all(|c| c.score.is_nan()));
     }
-    if max_score > 1 {
-        println!(
-            "the input '{}', decoded with '{}' gave the result '{}'",
-            input.to_string(),
-            key.to_string(),
-            best_fit
+
+    #[test]
+    fn frequency_recognition() {
+        // challenge 3 of set 1
+        let input =
+            Hex::try_from("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736")
+                .unwrap();
+        let candidates = single_byte_xor(&input, &FrequencyScorer::default(), 5);
+
+        assert_eq!(candidates.len(), 5);
+        assert_eq!(candidates[0].key, HexSymbol::new(b'X'));
+        assert_eq!(
+            candidates[0].plaintext.decode_lossy(),
+            "Cooking MC's like a pound of bacon"
         );
     }
-}
 
-pub fn single_byte_xor_word_recognition(dictionary: &Dictionary, input: Hex) {
-    for byte in u8::MIN..=u8::MAX {
-        let hs = HexSymbol::new(byte);
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_recognitions() {
+        let input = Hex::encode("Now that the party is jumping").rolling_xor(&Hex::encode("5"));
+        let frequency = ByteFrequency::english();
+        let expected = single_byte_xor(&input, &FrequencyScorer::new(frequency.clone()), 1);
 
-        let decoded = input.rolling_xor(&Hex::new(hs));
-        let decoded_str = decoded.decode();
+        let candidates = single_byte_xor_frequency_recognition(&frequency, &input, 1);
+        assert_eq!(candidates[0].plaintext, expected[0].plaintext);
+        assert_eq!(candidates[0].score, expected[0].score);
+        assert_eq!(candidates[0].breakdown[0].0, "log_likelihood");
 
-        if decoded.iter().fold(true, |acc, elem| {
-            acc && elem.is_printable() && !elem.is_extended()
-        }) && dictionary.hits(&decoded_str) > (&decoded_str.len() / 10) as u32
-        {
-            println!(
-                "the input '{}' gave the result '{}'",
-                input.to_string(),
-                &decoded_str
-            );
-        }
+        // the scorer runs once per key
+        let mut calls = 0;
+        let candidates = single_byte_xor_candidates(&input, 256, |plaintext| {
+            calls += 1;
+            if plaintext.decode() == b"Now that the party is jumping" {
+                Some(vec![])
+            } else {
+                None
+            }
+        });
+        assert_eq!(calls, 256);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].key, HexSymbol::new(b'5'));
+    }
+
+    #[test]
+    fn detect_challenge_4() {
+        let inputs: Vec<Hex> = fs::read_to_string("data/challenges/4.txt")
+            .unwrap()
+            .lines()
+            .map(|line| Hex::try_from(line).unwrap())
+            .collect();
+        let scorer = FrequencyScorer::default();
+
+        let candidates = detect_single_byte_xor(&inputs, &scorer, 5);
+        assert_eq!(candidates.len(), 5);
+        assert_eq!(candidates[0].line, 170);
+        assert_eq!(candidates[0].candidate.key, HexSymbol::new(0x35));
+        assert_eq!(
+            candidates[0].candidate.plaintext.decode_lossy(),
+            "Now that the party is jumping\n"
+        );
+
+        assert_eq!(
+            detect_single_byte_xor_parallel(&inputs, &scorer, 5, 4),
+            candidates
+        );
     }
 }
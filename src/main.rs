@@ -1,12 +1,12 @@
+pub mod analysis;
 pub mod attack;
+mod random;
+mod rank;
 pub mod structs;
+#[cfg(test)]
+mod testing;
 
-use std::convert::TryFrom;
-
-use std::fs;
-
-use attack::xor_cipher;
-use structs::{Base64, Dictionary, Hex};
+use structs::Hex;
 
 fn main() {
     let test = Hex::encode("this is a test");
@@ -0,0 +1,33 @@
+/// seed of the generators, any non-zero value
+const SEED: u32 = 0x2545_f491;
+
+/// deterministic xorshift generator, for the restarts of the hill climbing and for the
+/// pseudo-random bytes of the tests
+pub(crate) struct Xorshift(u32);
+
+impl Xorshift {
+    fn step(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    /// pseudo-random number in `0..bound`
+    pub(crate) fn next(&mut self, bound: usize) -> usize {
+        self.step() as usize % bound
+    }
+}
+
+impl Default for Xorshift {
+    fn default() -> Self {
+        Self(SEED)
+    }
+}
+
+/// deterministic pseudo-random bytes, e.g. a keystream or noise
+#[cfg(test)]
+pub(crate) fn bytes(length: usize) -> Vec<u8> {
+    let mut random = Xorshift::default();
+    (0..length).map(|_| (random.step() >> 24) as u8).collect()
+}
@@ -0,0 +1,43 @@
+/// key of the items ranked first, NaN being replaced so that it ranks last
+fn key(value: f64, last: f64) -> f64 {
+    if value.is_nan() {
+        last
+    } else {
+        value
+    }
+}
+
+/// sort the items by decreasing key (e.g. a score), NaN keys ranking last
+///
+/// the sort is stable: items with equal keys keep their order, so that the first ones
+/// given (e.g. the smallest key byte, the first line) come first
+pub(crate) fn descending<T, F: Fn(&T) -> f64>(items: &mut [T], key_of: F) {
+    items.sort_by(|a, b| {
+        key(key_of(b), f64::NEG_INFINITY).total_cmp(&key(key_of(a), f64::NEG_INFINITY))
+    });
+}
+
+/// sort the items by increasing key (e.g. a distance), NaN keys ranking last, with the
+/// same stability as `descending`
+pub(crate) fn ascending<T, F: Fn(&T) -> f64>(items: &mut [T], key_of: F) {
+    items.sort_by(|a, b| key(key_of(a), f64::INFINITY).total_cmp(&key(key_of(b), f64::INFINITY)));
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{ascending, descending};
+
+    #[test]
+    fn nan_last() {
+        let mut items = vec![(0, 1.0), (1, f64::NAN), (2, -f64::NAN), (3, 2.0), (4, 1.0)];
+        descending(&mut items, |(_, key)| *key);
+        let order: Vec<usize> = items.iter().map(|(i, _)| *i).collect();
+        assert_eq!(order, vec![3, 0, 4, 1, 2]);
+
+        ascending(&mut items, |(_, key)| *key);
+        let order: Vec<usize> = items.iter().map(|(i, _)| *i).collect();
+        assert_eq!(order, vec![0, 4, 3, 1, 2]);
+    }
+}
@@ -0,0 +1,310 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::structs::CodecError;
+
+const PREFIX: &str = "<~";
+const SUFFIX: &str = "~>";
+
+/********************************* HELPERS ***********************************/
+
+/// encode groups of 4 bytes as 5 base85 digits, a partial last group of n bytes
+/// being padded with zeros and written with n + 1 digits
+fn encode_groups(input: &[u8], symbol: impl Fn(u8) -> char, zero_shortcut: bool) -> String {
+    let mut output = String::with_capacity((input.len() / 4 + 1) * 5);
+    for group in input.chunks(4) {
+        if zero_shortcut && group == [0, 0, 0, 0] {
+            output.push('z');
+            continue;
+        }
+
+        let mut tab = [0u8; 4];
+        tab[..group.len()].copy_from_slice(group);
+        let mut value = u32::from_be_bytes(tab);
+
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = (value % 85) as u8;
+            value /= 85;
+        }
+        output.extend(digits.iter().take(group.len() + 1).map(|d| symbol(*d)));
+    }
+    output
+}
+
+/// decode groups of 5 base85 digits to 4 bytes, a partial last group of n digits
+/// being padded with the highest digit and decoded to n - 1 bytes
+fn decode_groups(
+    input: &str,
+    value: impl Fn(char) -> Option<u8>,
+    zero_shortcut: bool,
+    skip_whitespace: bool,
+) -> Result<Vec<u8>, CodecError> {
+    let mut output: Vec<u8> = Vec::with_capacity(input.len() * 4 / 5);
+    let mut group: Vec<(usize, u8)> = Vec::with_capacity(5);
+    let mut length: usize = 0;
+
+    for (offset, c) in input.char_indices() {
+        if skip_whitespace && c.is_whitespace() {
+            continue;
+        }
+        if zero_shortcut && c == 'z' && group.is_empty() {
+            output.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+
+        group.push((
+            offset,
+            value(c).ok_or(CodecError::InvalidCharacter {
+                character: c,
+                offset,
+            })?,
+        ));
+        length += 1;
+        if group.len() == 5 {
+            output.extend_from_slice(&decode_group(&group)?);
+            group.clear();
+        }
+    }
+
+    match group.len() {
+        0 => {}
+        1 => return Err(CodecError::InvalidLength(length)),
+        n => {
+            let bytes = n - 1;
+            group.resize(5, (group[0].0, 84));
+            output.extend_from_slice(&decode_group(&group)?[..bytes]);
+        }
+    }
+    Ok(output)
+}
+
+fn decode_group(group: &[(usize, u8)]) -> Result<[u8; 4], CodecError> {
+    let value = group
+        .iter()
+        .fold(0u64, |value, (_, digit)| value * 85 + *digit as u64);
+
+    u32::try_from(value)
+        .map(|value| value.to_be_bytes())
+        .map_err(|_| CodecError::GroupOverflow { offset: group[0].0 })
+}
+
+/********************************* ASCII85 ***********************************/
+
+/// btoa / Adobe flavour of base85, with the `z` shortcut for groups of zeros
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ascii85(Vec<u8>);
+
+impl Ascii85 {
+    /// encode the bytes of an utf-8 string to ascii85
+    pub fn encode(input: &str) -> Self {
+        Self::encode_bytes(input.as_bytes())
+    }
+
+    /// encode arbitrary binary data to ascii85
+    pub fn encode_bytes(input: &[u8]) -> Self {
+        Ascii85(input.to_vec())
+    }
+
+    /// decode the ascii85 to a string, replacing invalid utf-8 sequences
+    pub fn decode(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    /// decode the ascii85 to the raw bytes it holds
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    /// display the ascii85 between the Adobe `<~` & `~>` delimiters
+    pub fn to_delimited_string(&self) -> String {
+        format!("{}{}{}", PREFIX, self, SUFFIX)
+    }
+}
+
+/***************************** TRAITS *****************************************/
+
+impl TryFrom<&str> for Ascii85 {
+    type Error = CodecError;
+
+    /// whitespace is ignored and the Adobe `<~` & `~>` delimiters are optional
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let (start, content) = match input.strip_prefix(PREFIX) {
+            Some(rest) => {
+                let end = rest.find(SUFFIX).ok_or(CodecError::BadPadding)?;
+                let trailing = &rest[end + SUFFIX.len()..];
+                if let Some(offset) = trailing.find(|c: char| !c.is_whitespace()) {
+                    return Err(CodecError::TrailingData {
+                        offset: PREFIX.len() + end + SUFFIX.len() + offset,
+                    });
+                }
+                (PREFIX.len(), &rest[..end])
+            }
+            None => (0, input),
+        };
+
+        let value = |c: char| match c {
+            '!'..='u' => Some(c as u8 - b'!'),
+            _ => None,
+        };
+        decode_groups(content, value, true, true)
+            .map(Ascii85)
+            .map_err(|e| e.with_offset(start))
+    }
+}
+
+impl fmt::Display for Ascii85 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            encode_groups(&self.0, |d| (d + b'!') as char, true)
+        )
+    }
+}
+
+/*********************************** Z85 *************************************/
+
+/// ZeroMQ flavour of base85, whose alphabet is safe to embed in source code
+///
+/// partial groups are handled as in ascii85, an extension to the Z85 spec which
+/// only allows inputs whose length is a multiple of 4
+#[derive(Debug, PartialEq, Clone)]
+pub struct Z85(Vec<u8>);
+
+impl Z85 {
+    fn alphabet() -> &'static str {
+        "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#"
+    }
+
+    /// encode the bytes of an utf-8 string to z85
+    pub fn encode(input: &str) -> Self {
+        Self::encode_bytes(input.as_bytes())
+    }
+
+    /// encode arbitrary binary data to z85
+    pub fn encode_bytes(input: &[u8]) -> Self {
+        Z85(input.to_vec())
+    }
+
+    /// decode the z85 to a string, replacing invalid utf-8 sequences
+    pub fn decode(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    /// decode the z85 to the raw bytes it holds
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+impl TryFrom<&str> for Z85 {
+    type Error = CodecError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let value = |c: char| Self::alphabet().find(c).map(|i| i as u8);
+        decode_groups(input, value, false, false).map(Z85)
+    }
+}
+
+impl fmt::Display for Z85 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = Self::alphabet().as_bytes();
+        write!(
+            f,
+            "{}",
+            encode_groups(&self.0, |d| alphabet[d as usize] as char, false)
+        )
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{Ascii85, Z85};
+    use crate::structs::CodecError;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn encode_input() {
+        assert_eq!(Ascii85::encode("").to_string(), "");
+        assert_eq!(
+            Ascii85::encode("hello world!").to_string(),
+            "BOu!rD]j7BEbo80"
+        );
+        assert_eq!(Ascii85::encode("hello").to_string(), "BOu!rDZ");
+        assert_eq!(
+            Ascii85::encode_bytes(&[0, 0, 0, 0, 1]).to_delimited_string(),
+            "<~z!<~>"
+        );
+    }
+
+    #[test]
+    fn decode_input() {
+        assert_eq!(
+            Ascii85::try_from("BOu!rD]j7BEbo80").unwrap().decode(),
+            "hello world!"
+        );
+        assert_eq!(
+            Ascii85::try_from("<~BOu!r\nDZ~>\n").unwrap().decode(),
+            "hello"
+        );
+        assert_eq!(
+            Ascii85::try_from("z!<").unwrap().decode_bytes(),
+            vec![0, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn encode_and_decode_bytes() {
+        let input: Vec<u8> = [0, 0, 0, 0].iter().copied().chain(0..=254).collect();
+
+        let encoded = Ascii85::encode_bytes(&input).to_string();
+        assert_eq!(
+            Ascii85::try_from(&encoded[..]).unwrap().decode_bytes(),
+            input
+        );
+
+        let encoded = Z85::encode_bytes(&input).to_string();
+        assert_eq!(Z85::try_from(&encoded[..]).unwrap().decode_bytes(), input);
+    }
+
+    #[test]
+    fn illegal_parsing() {
+        assert_eq!(
+            Ascii85::try_from("<~BOu!rDv~>"),
+            Err(CodecError::InvalidCharacter {
+                character: 'v',
+                offset: 8
+            })
+        );
+        assert_eq!(
+            Ascii85::try_from("<~BOu!rDZ~>x"),
+            Err(CodecError::TrailingData { offset: 11 })
+        );
+        assert_eq!(Ascii85::try_from("<~BOu!rDZ"), Err(CodecError::BadPadding));
+        assert_eq!(
+            Ascii85::try_from("BOu!rD"),
+            Err(CodecError::InvalidLength(6))
+        );
+        assert_eq!(
+            Ascii85::try_from("uuuuu"),
+            Err(CodecError::GroupOverflow { offset: 0 })
+        );
+    }
+
+    #[test]
+    fn z85_spec_vector() {
+        let bytes = vec![0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(Z85::encode_bytes(&bytes).to_string(), "HelloWorld");
+        assert_eq!(Z85::try_from("HelloWorld").unwrap().decode_bytes(), bytes);
+        assert_eq!(
+            Z85::try_from("Hello World"),
+            Err(CodecError::InvalidCharacter {
+                character: ' ',
+                offset: 5
+            })
+        );
+    }
+}
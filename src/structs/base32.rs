@@ -0,0 +1,205 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::structs::CodecError;
+
+const PADDING_SYMBOL: char = '=';
+
+/********************************** BASE32 ***********************************/
+
+/// RFC 4648 base32, as used by TOTP secrets
+#[derive(Debug, PartialEq, Clone)]
+pub struct Base32(Vec<u8>);
+
+impl Base32 {
+    fn alphabet() -> &'static str {
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"
+    }
+
+    /// encode the bytes of an utf-8 string to base32
+    pub fn encode(input: &str) -> Self {
+        Self::encode_bytes(input.as_bytes())
+    }
+
+    /// encode arbitrary binary data to base32
+    pub fn encode_bytes(input: &[u8]) -> Self {
+        Base32(input.to_vec())
+    }
+
+    /// decode the base32 to a string, replacing invalid utf-8 sequences
+    pub fn decode(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    /// decode the base32 to the raw bytes it holds
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    /// parse a base32 string ignoring whitespace and case, with optional padding
+    ///
+    /// error offsets refer to the input stripped of its whitespace
+    pub fn parse_lenient(input: &str) -> Result<Self, CodecError> {
+        let stripped: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        Self::parse(&stripped, false)
+    }
+
+    fn parse(input: &str, padding_required: bool) -> Result<Self, CodecError> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 5 / 8);
+        let mut buffer: u32 = 0;
+        let mut bits: u8 = 0;
+        let mut length: usize = 0;
+        let mut padding: usize = 0;
+
+        for (offset, c) in input.char_indices() {
+            if c == PADDING_SYMBOL {
+                padding += 1;
+                continue;
+            } else if padding > 0 {
+                return Err(CodecError::TrailingData { offset });
+            }
+
+            let value = Self::alphabet()
+                .find(c)
+                .ok_or(CodecError::InvalidCharacter {
+                    character: c,
+                    offset,
+                })?;
+
+            length += 1;
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+
+        // a group of 8 symbols holds 5 bytes, a partial one 2, 4, 5 or 7 symbols
+        if [1, 3, 6].contains(&(length % 8)) {
+            return Err(CodecError::InvalidLength(length));
+        }
+        if padding >= 8
+            || ((padding_required || padding > 0) && !(length + padding).is_multiple_of(8))
+        {
+            return Err(CodecError::BadPadding);
+        }
+
+        Ok(Base32(bytes))
+    }
+}
+
+/***************************** TRAITS *****************************************/
+
+impl TryFrom<&str> for Base32 {
+    type Error = CodecError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input, true)
+    }
+}
+
+impl fmt::Display for Base32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = Self::alphabet().as_bytes();
+        let mut symbols = String::with_capacity((self.0.len() / 5 + 1) * 8);
+        let mut buffer: u32 = 0;
+        let mut bits: u8 = 0;
+
+        for byte in self.0.iter() {
+            buffer = (buffer << 8) | *byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                symbols.push(alphabet[((buffer >> bits) & 0b1_1111) as usize] as char);
+            }
+            buffer &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            symbols.push(alphabet[((buffer << (5 - bits)) & 0b1_1111) as usize] as char);
+        }
+
+        let padding = (8 - symbols.len() % 8) % 8;
+        write!(
+            f,
+            "{}{}",
+            symbols,
+            PADDING_SYMBOL.to_string().repeat(padding)
+        )
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Base32;
+    use super::CodecError;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn encode_input() {
+        // RFC 4648 test vectors
+        assert_eq!(Base32::encode("").to_string(), "");
+        assert_eq!(Base32::encode("f").to_string(), "MY======");
+        assert_eq!(Base32::encode("fo").to_string(), "MZXQ====");
+        assert_eq!(Base32::encode("foo").to_string(), "MZXW6===");
+        assert_eq!(Base32::encode("foob").to_string(), "MZXW6YQ=");
+        assert_eq!(Base32::encode("fooba").to_string(), "MZXW6YTB");
+        assert_eq!(Base32::encode("foobar").to_string(), "MZXW6YTBOI======");
+    }
+
+    #[test]
+    fn decode_input() {
+        assert_eq!(Base32::try_from("MZXW6YQ=").unwrap().decode(), "foob");
+        assert_eq!(
+            Base32::try_from("MZXW6YTBOI======").unwrap().decode(),
+            "foobar"
+        );
+    }
+
+    #[test]
+    fn encode_and_decode_bytes() {
+        let input: Vec<u8> = (0..=255).collect();
+        let encoded = Base32::encode_bytes(&input).to_string();
+        assert_eq!(
+            Base32::try_from(&encoded[..]).unwrap().decode_bytes(),
+            input
+        );
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        // TOTP secrets are often displayed lowercase, grouped and unpadded
+        assert_eq!(
+            Base32::parse_lenient("mzxw 6ytb oi").unwrap().decode(),
+            "foobar"
+        );
+        assert_eq!(Base32::try_from("MZXW6YTBOI"), Err(CodecError::BadPadding));
+    }
+
+    #[test]
+    fn illegal_parsing() {
+        assert_eq!(
+            Base32::try_from("MZXW1==="),
+            Err(CodecError::InvalidCharacter {
+                character: '1',
+                offset: 4
+            })
+        );
+        assert_eq!(
+            Base32::try_from("MZX====="),
+            Err(CodecError::InvalidLength(3))
+        );
+        assert_eq!(Base32::try_from("MZXW6=="), Err(CodecError::BadPadding));
+        assert_eq!(
+            Base32::try_from("MY======MY======"),
+            Err(CodecError::TrailingData { offset: 8 })
+        );
+    }
+}
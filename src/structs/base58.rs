@@ -0,0 +1,160 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::structs::CodecError;
+
+/********************************** BASE58 ***********************************/
+
+/// base58 with the bitcoin alphabet (no `0`, `O`, `I` & `l`), without checksum
+#[derive(Debug, PartialEq, Clone)]
+pub struct Base58(Vec<u8>);
+
+impl Base58 {
+    fn alphabet() -> &'static str {
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+    }
+
+    /// encode the bytes of an utf-8 string to base58
+    pub fn encode(input: &str) -> Self {
+        Self::encode_bytes(input.as_bytes())
+    }
+
+    /// encode arbitrary binary data to base58
+    pub fn encode_bytes(input: &[u8]) -> Self {
+        Base58(input.to_vec())
+    }
+
+    /// decode the base58 to a string, replacing invalid utf-8 sequences
+    pub fn decode(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    /// decode the base58 to the raw bytes it holds
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+/***************************** TRAITS *****************************************/
+
+impl TryFrom<&str> for Base58 {
+    type Error = CodecError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        // each leading '1' stands for a leading zero byte
+        let zeros = input.chars().take_while(|c| *c == '1').count();
+
+        // base 256 digits of the number, least significant first
+        let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+        for (offset, c) in input.char_indices().skip(zeros) {
+            let mut carry = Self::alphabet()
+                .find(c)
+                .ok_or(CodecError::InvalidCharacter {
+                    character: c,
+                    offset,
+                })? as u32;
+
+            for byte in bytes.iter_mut() {
+                carry += *byte as u32 * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        Ok(Base58(
+            vec![0u8; zeros]
+                .into_iter()
+                .chain(bytes.into_iter().rev())
+                .collect(),
+        ))
+    }
+}
+
+impl fmt::Display for Base58 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = Self::alphabet().as_bytes();
+        let zeros = self.0.iter().take_while(|b| **b == 0).count();
+
+        // base 58 digits of the number, least significant first
+        let mut digits: Vec<u8> = Vec::with_capacity(self.0.len() * 138 / 100 + 1);
+        for byte in self.0[zeros..].iter() {
+            let mut carry = *byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        write!(
+            f,
+            "{}{}",
+            "1".repeat(zeros),
+            digits
+                .iter()
+                .rev()
+                .map(|d| alphabet[*d as usize] as char)
+                .collect::<String>()
+        )
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Base58;
+    use super::CodecError;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn encode_input() {
+        assert_eq!(Base58::encode("").to_string(), "");
+        assert_eq!(Base58::encode("hello world").to_string(), "StV1DL6CwTryKyV");
+        assert_eq!(
+            Base58::encode_bytes(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]).to_string(),
+            "11233QC4"
+        );
+    }
+
+    #[test]
+    fn decode_input() {
+        assert_eq!(
+            Base58::try_from("StV1DL6CwTryKyV").unwrap().decode(),
+            "hello world"
+        );
+        assert_eq!(
+            Base58::try_from("11233QC4").unwrap().decode_bytes(),
+            vec![0, 0, 0x28, 0x7f, 0xb4, 0xcd]
+        );
+    }
+
+    #[test]
+    fn encode_and_decode_bytes() {
+        let input: Vec<u8> = [0, 0, 0].iter().copied().chain(0..=255).collect();
+        let encoded = Base58::encode_bytes(&input).to_string();
+        assert_eq!(
+            Base58::try_from(&encoded[..]).unwrap().decode_bytes(),
+            input
+        );
+    }
+
+    #[test]
+    fn illegal_parsing() {
+        assert_eq!(
+            Base58::try_from("StV1DL0CwTryKyV"),
+            Err(CodecError::InvalidCharacter {
+                character: '0',
+                offset: 6
+            })
+        );
+    }
+}
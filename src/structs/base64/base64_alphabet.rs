@@ -0,0 +1,147 @@
+use crate::structs::CodecError;
+
+/// symbol appended to padded base64 so that its length is a multiple of 4
+pub const PADDING_SYMBOL: char = '=';
+
+/****************************** BASE64 ALPHABET ******************************/
+
+/// the 64 symbols used to represent base64 sextets, along with the padding policy
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Base64Alphabet {
+    symbols: [u8; 64],
+    padded: bool,
+}
+
+impl Base64Alphabet {
+    /// RFC 4648 standard alphabet (`+/`), padded with `=`
+    pub const STANDARD: Self = Self {
+        symbols: *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+        padded: true,
+    };
+
+    /// RFC 4648 standard alphabet (`+/`), without padding
+    pub const STANDARD_NO_PAD: Self = Self {
+        symbols: Self::STANDARD.symbols,
+        padded: false,
+    };
+
+    /// RFC 4648 url and filename safe alphabet (`-_`), padded with `=`
+    pub const URL_SAFE: Self = Self {
+        symbols: *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        padded: true,
+    };
+
+    /// RFC 4648 url and filename safe alphabet (`-_`), without padding (JWT, cookies, ...)
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        symbols: Self::URL_SAFE.symbols,
+        padded: false,
+    };
+
+    /// build an alphabet from 64 distinct ascii symbols, none of them being the padding symbol
+    pub fn custom(symbols: &str, padded: bool) -> Result<Self, CodecError> {
+        let bytes = symbols.as_bytes();
+        if bytes.len() != 64
+            || !symbols.is_ascii()
+            || symbols.contains(PADDING_SYMBOL)
+            || bytes
+                .iter()
+                .enumerate()
+                .any(|(i, c)| bytes[i + 1..].contains(c))
+        {
+            return Err(CodecError::InvalidAlphabet);
+        }
+
+        let mut tab = [0u8; 64];
+        tab.copy_from_slice(bytes);
+        Ok(Self {
+            symbols: tab,
+            padded,
+        })
+    }
+
+    /// same symbols, with the given padding policy
+    pub fn with_padding(self, padded: bool) -> Self {
+        Self { padded, ..self }
+    }
+
+    pub fn is_padded(&self) -> bool {
+        self.padded
+    }
+
+    /// the symbol representing the sextet `value` (< 64)
+    pub fn symbol(&self, value: u8) -> char {
+        self.symbols[value as usize] as char
+    }
+
+    /// the sextet represented by `symbol`, if it belongs to the alphabet
+    pub fn value(&self, symbol: char) -> Option<u8> {
+        self.symbols
+            .iter()
+            .position(|c| *c as char == symbol)
+            .map(|i| i as u8)
+    }
+}
+
+impl Default for Base64Alphabet {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Base64Alphabet;
+    use super::CodecError;
+
+    #[test]
+    fn symbols_and_values() {
+        let alphabet = Base64Alphabet::STANDARD;
+        assert_eq!(alphabet.symbol(0), 'A');
+        assert_eq!(alphabet.symbol(63), '/');
+        assert_eq!(alphabet.value('a'), Some(26));
+        assert_eq!(alphabet.value('-'), None);
+
+        let alphabet = Base64Alphabet::URL_SAFE_NO_PAD;
+        assert_eq!(alphabet.symbol(62), '-');
+        assert_eq!(alphabet.value('_'), Some(63));
+        assert!(!alphabet.is_padded());
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let reversed: String = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            .chars()
+            .rev()
+            .collect();
+        let alphabet = Base64Alphabet::custom(&reversed, true).unwrap();
+        assert_eq!(alphabet.symbol(0), '/');
+        assert_eq!(alphabet.value('A'), Some(63));
+    }
+
+    #[test]
+    fn illegal_custom_alphabet() {
+        // too short
+        assert_eq!(
+            Base64Alphabet::custom("ABC", true),
+            Err(CodecError::InvalidAlphabet)
+        );
+        // duplicated symbol
+        assert_eq!(
+            Base64Alphabet::custom(
+                "AACDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+                true
+            ),
+            Err(CodecError::InvalidAlphabet)
+        );
+        // padding symbol
+        assert_eq!(
+            Base64Alphabet::custom(
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+=",
+                false
+            ),
+            Err(CodecError::InvalidAlphabet)
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::io::{self, Read, Write};
+
+use super::base64_alphabet::{Base64Alphabet, PADDING_SYMBOL};
+use crate::structs::CodecError;
+
+/// size of the chunks read from, or written to, the inner stream
+const BUFFER_SIZE: usize = 4096;
+
+/****************************** BASE64 ENCODER *******************************/
+
+/// writer encoding everything written to it as base64 into the inner writer
+///
+/// the last incomplete group of bytes is only written by `finish`
+pub struct Base64Encoder<W: Write> {
+    inner: W,
+    alphabet: Base64Alphabet,
+    // bytes waiting for a complete group of 3
+    rest: Vec<u8>,
+}
+
+impl<W: Write> Base64Encoder<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_alphabet(inner, Base64Alphabet::STANDARD)
+    }
+
+    pub fn with_alphabet(inner: W, alphabet: Base64Alphabet) -> Self {
+        Self {
+            inner,
+            alphabet,
+            rest: Vec::with_capacity(3),
+        }
+    }
+
+    /// encode a group of at most 3 bytes into the output buffer
+    fn encode_group(&self, group: &[u8], output: &mut Vec<u8>) {
+        let mut tab = [0u8; 3];
+        tab[..group.len()].copy_from_slice(group);
+        let sextets = [
+            tab[0] >> 2,
+            ((tab[0] & 0b0000_0011) << 4) | (tab[1] >> 4),
+            ((tab[1] & 0b0000_1111) << 2) | (tab[2] >> 6),
+            tab[2] & 0b0011_1111,
+        ];
+
+        for sextet in sextets.iter().take(group.len() + 1) {
+            output.push(self.alphabet.symbol(*sextet) as u8);
+        }
+        if self.alphabet.is_padded() {
+            for _ in group.len()..3 {
+                output.push(PADDING_SYMBOL as u8);
+            }
+        }
+    }
+
+    /// write the last incomplete group (with its padding) and return the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut output = Vec::with_capacity(4);
+        if !self.rest.is_empty() {
+            self.encode_group(&self.rest, &mut output);
+            self.rest.clear();
+        }
+        self.inner.write_all(&output)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Base64Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // never encode more than BUFFER_SIZE bytes at once to keep memory bounded
+        let buf = &buf[..buf.len().min(BUFFER_SIZE)];
+
+        let mut output = Vec::with_capacity((buf.len() / 3 + 1) * 4);
+        let mut tail = buf;
+
+        // complete the group started by a previous write
+        if !self.rest.is_empty() {
+            let (head, rest) = tail.split_at((3 - self.rest.len()).min(tail.len()));
+            self.rest.extend_from_slice(head);
+            if self.rest.len() < 3 {
+                return Ok(buf.len());
+            }
+            let group = std::mem::take(&mut self.rest);
+            self.encode_group(&group, &mut output);
+            tail = rest;
+        }
+
+        let mut groups = tail.chunks_exact(3);
+        for group in &mut groups {
+            self.encode_group(group, &mut output);
+        }
+        self.rest = groups.remainder().to_vec();
+
+        self.inner.write_all(&output)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/****************************** BASE64 DECODER *******************************/
+
+/// reader decoding the base64 read from the inner reader, ignoring whitespace
+pub struct Base64Decoder<R: Read> {
+    inner: R,
+    alphabet: Base64Alphabet,
+    // bits decoded but not yet gathered in a full byte
+    bits: u32,
+    bits_count: u8,
+    symbols_count: usize,
+    padding_count: usize,
+    // number of bytes read from the inner reader
+    offset: usize,
+    // bytes decoded but not yet read
+    decoded: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+impl<R: Read> Base64Decoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_alphabet(inner, Base64Alphabet::STANDARD)
+    }
+
+    pub fn with_alphabet(inner: R, alphabet: Base64Alphabet) -> Self {
+        Self {
+            inner,
+            alphabet,
+            bits: 0,
+            bits_count: 0,
+            symbols_count: 0,
+            padding_count: 0,
+            offset: 0,
+            decoded: Vec::with_capacity(BUFFER_SIZE),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode_symbol(&mut self, symbol: u8) -> Result<(), CodecError> {
+        let offset = self.offset;
+        self.offset += 1;
+        if (symbol as char).is_ascii_whitespace() {
+            return Ok(());
+        }
+        if symbol as char == PADDING_SYMBOL {
+            self.padding_count += 1;
+            return Ok(());
+        }
+
+        // no data can follow the padding
+        if self.padding_count > 0 {
+            return Err(CodecError::TrailingData { offset });
+        }
+        let value = self
+            .alphabet
+            .value(symbol as char)
+            .ok_or(CodecError::InvalidCharacter {
+                character: symbol as char,
+                offset,
+            })?;
+
+        self.symbols_count += 1;
+        self.bits = (self.bits << 6) | value as u32;
+        self.bits_count += 6;
+        if self.bits_count >= 8 {
+            self.bits_count -= 8;
+            self.decoded.push((self.bits >> self.bits_count) as u8);
+            self.bits &= (1 << self.bits_count) - 1;
+        }
+        Ok(())
+    }
+
+    /// check the length & padding of the whole input once it has been read
+    fn check_end(&self) -> Result<(), CodecError> {
+        let length = self.symbols_count;
+        if length % 4 == 1 {
+            return Err(CodecError::InvalidLength(length));
+        }
+        if self.padding_count > 2
+            || (!self.alphabet.is_padded() && self.padding_count > 0)
+            || (self.alphabet.is_padded() && !(length + self.padding_count).is_multiple_of(4))
+        {
+            return Err(CodecError::BadPadding);
+        }
+        Ok(())
+    }
+
+    /// read the inner reader until some bytes are decoded or the end is reached
+    fn fill(&mut self) -> io::Result<()> {
+        self.decoded.clear();
+        self.position = 0;
+
+        let mut raw = [0u8; BUFFER_SIZE];
+        while self.decoded.is_empty() && !self.eof {
+            let n = self.inner.read(&mut raw)?;
+            if n == 0 {
+                self.eof = true;
+                self.check_end()?;
+            }
+            for symbol in raw[..n].iter() {
+                self.decode_symbol(*symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Base64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.decoded.len() {
+            self.fill()?;
+        }
+
+        let available = &self.decoded[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{Base64Decoder, Base64Encoder};
+    use crate::structs::{Base64, Base64Alphabet, CodecError};
+    use crate::testing::read_error;
+    use std::io::{Read, Write};
+
+    fn encode_stream(input: &[u8], alphabet: Base64Alphabet, chunk: usize) -> String {
+        let mut encoder = Base64Encoder::with_alphabet(Vec::new(), alphabet);
+        for part in input.chunks(chunk) {
+            encoder.write_all(part).unwrap();
+        }
+        String::from_utf8(encoder.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn encoder_matches_base64() {
+        let input: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = Base64::encode_bytes(&input).to_string();
+
+        for chunk in [1, 2, 3, 7, 1000].iter() {
+            assert_eq!(
+                encode_stream(&input, Base64Alphabet::STANDARD, *chunk),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn encoder_padding() {
+        assert_eq!(encode_stream(b"A", Base64Alphabet::STANDARD, 1), "QQ==");
+        assert_eq!(encode_stream(b"AA", Base64Alphabet::STANDARD, 1), "QUE=");
+        assert_eq!(
+            encode_stream(b"AA", Base64Alphabet::URL_SAFE_NO_PAD, 1),
+            "QUE"
+        );
+        assert_eq!(encode_stream(b"", Base64Alphabet::STANDARD, 1), "");
+    }
+
+    #[test]
+    fn decoder_matches_base64() {
+        let input: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let encoded = Base64::encode_bytes(&input).to_wrapped_string(60);
+
+        let mut decoded = Vec::new();
+        Base64Decoder::new(encoded.as_bytes())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, input);
+    }
+
+    #[test]
+    fn decoder_with_alphabet() {
+        let mut decoded = Vec::new();
+        Base64Decoder::with_alphabet("-_8".as_bytes(), Base64Alphabet::URL_SAFE_NO_PAD)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, vec![0xfb, 0xff]);
+    }
+
+    #[test]
+    fn decoder_illegal_input() {
+        assert_eq!(
+            read_error(Base64Decoder::new(&b"g%x="[..])),
+            CodecError::InvalidCharacter {
+                character: '%',
+                offset: 1
+            }
+        );
+        assert_eq!(
+            read_error(Base64Decoder::new(&b"QQ="[..])),
+            CodecError::BadPadding
+        );
+        assert_eq!(
+            read_error(Base64Decoder::new(&b"QQ==\nQQ=="[..])),
+            CodecError::TrailingData { offset: 5 }
+        );
+        assert_eq!(
+            read_error(Base64Decoder::new(&b"QUFBQ"[..])),
+            CodecError::InvalidLength(5)
+        );
+    }
+}
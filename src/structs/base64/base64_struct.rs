@@ -0,0 +1,507 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::ops::Deref;
+use std::path::Path;
+
+use super::base64_alphabet::{Base64Alphabet, PADDING_SYMBOL};
+use crate::structs::{CodecError, Hex};
+
+/// line width used by MIME (RFC 2045) for base64 content
+pub const MIME_LINE_WIDTH: usize = 76;
+
+/********************************** BASE64 ***********************************/
+#[derive(Debug, PartialEq)]
+pub struct Base64(Vec<u8>);
+
+impl Base64 {
+    /// encode the bytes of an utf-8 string to base64
+    pub fn encode(input: &str) -> Self {
+        Self::encode_bytes(input.as_bytes())
+    }
+
+    /// encode arbitrary binary data to base64
+    pub fn encode_bytes(input: &[u8]) -> Self {
+        Base64(
+            input
+                .chunks(3)
+                .map(Vec::from)
+                .collect::<Vec<Vec<u8>>>()
+                .iter()
+                .fold(Vec::new(), |acc, xs| {
+                    let mut tab: Vec<u8> = Vec::new();
+                    let last_byte = xs.iter().enumerate().fold(0u8, |rest, (i, byte)| {
+                        tab.push((rest + (byte >> (2 * (i + 1)))) & 0b0011_1111);
+                        byte << (6 - (2 * (i + 1)))
+                    });
+                    tab.push(last_byte & 0b0011_1111);
+                    let padding = xs.len() % 3;
+                    if padding == 1 {
+                        tab.push(64u8);
+                        tab.push(64u8);
+                    } else if padding == 2 {
+                        tab.push(64u8);
+                    }
+                    [acc, tab].concat()
+                }),
+        )
+    }
+
+    /// parse a base64 string ignoring any whitespace, such as line breaks of wrapped files
+    ///
+    /// error offsets refer to the input stripped of its whitespace
+    pub fn parse_lenient(input: &str) -> Result<Self, CodecError> {
+        let stripped: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        Self::try_from(&stripped[..])
+    }
+
+    /// load a whole (possibly line-wrapped) base64 file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, CodecError> {
+        let content = fs::read_to_string(path)?;
+        Self::parse_lenient(&content)
+    }
+
+    /// parse a base64 string written with the given alphabet and padding policy
+    pub fn parse_with(input: &str, alphabet: &Base64Alphabet) -> Result<Self, CodecError> {
+        // for each char, get the u8 position (<64) of the char in the given alphabet
+        // and count the trailing '=' to get padding
+        let mut symbols: Vec<u8> = Vec::with_capacity(input.len());
+        let mut padding: usize = 0;
+        for (offset, c) in input.char_indices() {
+            if c == PADDING_SYMBOL {
+                padding += 1;
+            } else if padding > 0 {
+                return Err(CodecError::TrailingData { offset });
+            } else {
+                symbols.push(alphabet.value(c).ok_or(CodecError::InvalidCharacter {
+                    character: c,
+                    offset,
+                })?);
+            }
+        }
+
+        // check lenght of input & padding length
+        //  - input length must be % 4 == 0 if padded, and never % 4 == 1
+        //  - padding length cannot be greater than 2, nor present if unpadded
+        let length = symbols.len();
+        if length % 4 == 1 {
+            return Err(CodecError::InvalidLength(length));
+        }
+        if padding > 2
+            || (!alphabet.is_padded() && padding > 0)
+            || (alphabet.is_padded() && !(length + padding).is_multiple_of(4))
+        {
+            return Err(CodecError::BadPadding);
+        }
+
+        Ok(Base64(symbols))
+    }
+
+    /// display the base64 with the given alphabet and padding policy
+    pub fn display_with<'a>(&'a self, alphabet: &'a Base64Alphabet) -> Base64Display<'a> {
+        Base64Display {
+            base_64: self,
+            alphabet,
+        }
+    }
+
+    /// display the base64 with a line break every `width` characters (no wrapping if 0)
+    pub fn to_wrapped_string(&self, width: usize) -> String {
+        let encoded = self.to_string();
+        if width == 0 {
+            return encoded;
+        }
+
+        encoded
+            .as_bytes()
+            .chunks(width)
+            .map(|line| String::from_utf8_lossy(line).into_owned())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// decode the base64 to a string, replacing invalid utf-8 sequences
+    pub fn decode(&self) -> String {
+        String::from_utf8_lossy(&self.decode_bytes()).into_owned()
+    }
+
+    /// decode the base64 to the raw bytes it holds
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        // padding symbols (64) carry no data
+        self.0
+            .iter()
+            .copied()
+            .filter(|c| *c < 64)
+            .collect::<Vec<u8>>()
+            .chunks(4)
+            .map(Vec::from)
+            .collect::<Vec<Vec<u8>>>()
+            .iter()
+            .fold(Vec::new(), |acc, xs| {
+                let decoded = xs[1..]
+                    .iter()
+                    .zip(xs.iter())
+                    .enumerate()
+                    // b is the elem i+1 & a is the elem i of the chunk &[u8;4]
+                    .map(|(i, (b, a))| (a << ((i + 1) * 2)) + (b >> (6 - (i + 1) * 2)))
+                    .collect::<Vec<u8>>();
+
+                [acc, decoded].concat()
+            })
+    }
+}
+
+/***************************** TRAITS *****************************************/
+
+impl Deref for Base64 {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<&Hex> for Base64 {
+    fn from(hex: &Hex) -> Self {
+        Self::encode_bytes(hex.as_bytes())
+    }
+}
+
+impl From<&Base64> for Hex {
+    fn from(base_64: &Base64) -> Self {
+        Hex::from(base_64.decode_bytes())
+    }
+}
+
+impl TryFrom<&str> for Base64 {
+    type Error = CodecError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse_with(input, &Base64Alphabet::STANDARD)
+    }
+}
+
+impl fmt::Display for Base64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_with(&Base64Alphabet::STANDARD))
+    }
+}
+
+/// helper displaying a base64 with a given alphabet, see `Base64::display_with`
+pub struct Base64Display<'a> {
+    base_64: &'a Base64,
+    alphabet: &'a Base64Alphabet,
+}
+
+impl fmt::Display for Base64Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // padding symbols (64) stored by `encode` are rebuilt from the length
+        let symbols: String = self
+            .base_64
+            .iter()
+            .filter(|c| **c < 64)
+            .map(|c| self.alphabet.symbol(*c))
+            .collect();
+
+        let padding = if self.alphabet.is_padded() {
+            (4 - symbols.len() % 4) % 4
+        } else {
+            0
+        };
+
+        write!(
+            f,
+            "{}{}",
+            symbols,
+            PADDING_SYMBOL.to_string().repeat(padding)
+        )
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+
+    use super::Base64;
+    use super::Base64Alphabet;
+    use super::CodecError;
+    use super::Hex;
+    use std::convert::TryFrom;
+    use std::io;
+
+    #[test]
+    fn count_alphabet() {
+        // 64 letters in the alphabet
+        let alphabet = Base64Alphabet::STANDARD;
+        assert_eq!(
+            (0..64)
+                .filter_map(|i| alphabet.value(alphabet.symbol(i)))
+                .count(),
+            64
+        );
+    }
+
+    #[test]
+    fn alphabet_without_equal() {
+        let alphabet = Base64Alphabet::STANDARD;
+
+        assert_eq!(alphabet.value('='), None);
+    }
+
+    #[test]
+    fn encode_input_padding_two() {
+        let value = "A";
+        assert_eq!(Base64::encode(value).to_string(), "QQ==".to_string());
+    }
+
+    #[test]
+    fn encode_input_padding_one() {
+        let value = "AA";
+        assert_eq!(Base64::encode(value).to_string(), "QUE=".to_string());
+    }
+
+    #[test]
+    fn encode_input_no_padding() {
+        let value = "AAA";
+        assert_eq!(Base64::encode(value).to_string(), "QUFB".to_string());
+    }
+
+    #[test]
+    fn encode_input() {
+        let value = "hello world!";
+        assert_eq!(
+            Base64::encode(value).to_string(),
+            "aGVsbG8gd29ybGQh".to_string()
+        );
+
+        let value = "hello world";
+        assert_eq!(
+            Base64::encode(value).to_string(),
+            "aGVsbG8gd29ybGQ=".to_string()
+        );
+
+        let value = "hello worl";
+        assert_eq!(
+            Base64::encode(value).to_string(),
+            "aGVsbG8gd29ybA==".to_string()
+        );
+    }
+
+    #[test]
+    fn from_input_padding_two() {
+        let input = "QQ==";
+        let base_64 = Base64::try_from(input).unwrap();
+        assert_eq!(base_64, Base64(vec!(16, 16)));
+    }
+
+    #[test]
+    fn from_input_padding_one() {
+        let input = "QUE=";
+        let base_64 = Base64::try_from(input).unwrap();
+        assert_eq!(base_64, Base64(vec!(16, 20, 4)));
+    }
+
+    #[test]
+    fn from_input_no_padding() {
+        let input = "QUFB";
+        let base_64 = Base64::try_from(input).unwrap();
+        assert_eq!(base_64, Base64(vec!(16, 20, 5, 1)));
+    }
+
+    #[test]
+    fn decode_input() {
+        let input = "aGVsbG8gd29ybGQh";
+        let decoded = Base64::try_from(input).unwrap().decode();
+        assert_eq!(decoded, "hello world!".to_string());
+    }
+
+    #[test]
+    fn encode_and_decode() {
+        let input = "hello world!";
+
+        let encoded = Base64::encode(input);
+        let decoded = encoded.decode();
+        assert_eq!(decoded, input.to_string());
+    }
+
+    #[test]
+    fn illegal_parsing() {
+        let input = "g%x";
+        assert_eq!(
+            Base64::try_from(input),
+            Err(CodecError::InvalidCharacter {
+                character: '%',
+                offset: 1
+            })
+        );
+
+        let input = "QQ=";
+        assert_eq!(Base64::try_from(input), Err(CodecError::BadPadding));
+
+        let input = "QQ==QQ==";
+        assert_eq!(
+            Base64::try_from(input),
+            Err(CodecError::TrailingData { offset: 4 })
+        );
+    }
+
+    #[test]
+    fn decode_padded_input() {
+        assert_eq!(Base64::encode("A").decode_bytes(), b"A".to_vec());
+        assert_eq!(Base64::encode("AA").decode_bytes(), b"AA".to_vec());
+        assert_eq!(
+            Base64::try_from("QUE=").unwrap().decode_bytes(),
+            b"AA".to_vec()
+        );
+    }
+
+    #[test]
+    fn encode_and_decode_bytes() {
+        let input: Vec<u8> = (0..=255).collect();
+
+        let encoded = Base64::encode_bytes(&input);
+        assert_eq!(encoded.decode_bytes(), input);
+
+        let parsed = Base64::try_from(&encoded.to_string()[..]).unwrap();
+        assert_eq!(parsed.decode_bytes(), input);
+    }
+
+    #[test]
+    fn hex_conversions() {
+        // challenge 1 of set 1
+        let hex = Hex::try_from(
+            "49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d",
+        )
+        .unwrap();
+        let base_64 = Base64::from(&hex);
+        assert_eq!(
+            base_64.to_string(),
+            "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t".to_string()
+        );
+        assert_eq!(Hex::from(&base_64), hex);
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        let input = "aGVsbG8g\r\nd29y bGQh\n";
+        assert_eq!(
+            Base64::try_from(input),
+            Err(CodecError::InvalidCharacter {
+                character: '\r',
+                offset: 8
+            })
+        );
+        assert_eq!(
+            Base64::parse_lenient(input).unwrap().decode(),
+            "hello world!".to_string()
+        );
+
+        assert_eq!(Base64::parse_lenient("QQ\n=="), Base64::try_from("QQ=="));
+        assert_eq!(
+            Base64::parse_lenient("g%\nx"),
+            Err(CodecError::InvalidCharacter {
+                character: '%',
+                offset: 1
+            })
+        );
+    }
+
+    #[test]
+    fn wrapped_display() {
+        let encoded = Base64::encode("hello world!");
+        assert_eq!(
+            encoded.to_wrapped_string(5),
+            "aGVsb\nG8gd2\n9ybGQ\nh".to_string()
+        );
+        assert_eq!(encoded.to_wrapped_string(0), encoded.to_string());
+        assert_eq!(
+            encoded.to_wrapped_string(super::MIME_LINE_WIDTH),
+            encoded.to_string()
+        );
+        assert_eq!(
+            Base64::parse_lenient(&encoded.to_wrapped_string(3))
+                .unwrap()
+                .decode(),
+            "hello world!".to_string()
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        assert_eq!(
+            Base64::from_file("data/challenges/does_not_exist.txt"),
+            Err(CodecError::Io(io::ErrorKind::NotFound))
+        );
+    }
+
+    #[test]
+    fn display_parsed_padding() {
+        assert_eq!(Base64::try_from("QQ==").unwrap().to_string(), "QQ==");
+        assert_eq!(Base64::try_from("QUE=").unwrap().to_string(), "QUE=");
+    }
+
+    #[test]
+    fn url_safe_alphabet() {
+        // 0xfb 0xff encodes to "+/8=" with the standard alphabet
+        let encoded = Base64::encode_bytes(&[0xfb, 0xff]);
+        assert_eq!(encoded.to_string(), "+/8=");
+        assert_eq!(
+            encoded.display_with(&Base64Alphabet::URL_SAFE).to_string(),
+            "-_8="
+        );
+        assert_eq!(
+            encoded
+                .display_with(&Base64Alphabet::URL_SAFE_NO_PAD)
+                .to_string(),
+            "-_8"
+        );
+
+        let parsed = Base64::parse_with("-_8", &Base64Alphabet::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(parsed.decode_bytes(), vec![0xfb, 0xff]);
+        assert_eq!(
+            Base64::parse_with("-_8=", &Base64Alphabet::URL_SAFE).unwrap(),
+            parsed
+        );
+    }
+
+    #[test]
+    fn illegal_parsing_with_alphabet() {
+        // wrong alphabet
+        assert_eq!(
+            Base64::parse_with("+/8=", &Base64Alphabet::URL_SAFE),
+            Err(CodecError::InvalidCharacter {
+                character: '+',
+                offset: 0
+            })
+        );
+        // padding where none is expected
+        assert_eq!(
+            Base64::parse_with("QQ==", &Base64Alphabet::STANDARD_NO_PAD),
+            Err(CodecError::BadPadding)
+        );
+        // missing padding where it is required
+        assert_eq!(Base64::try_from("QQ"), Err(CodecError::BadPadding));
+        // a single trailing symbol cannot hold a byte
+        assert_eq!(
+            Base64::parse_with("QUFBQ", &Base64Alphabet::STANDARD_NO_PAD),
+            Err(CodecError::InvalidLength(5))
+        );
+    }
+
+    #[test]
+    fn custom_alphabet_round_trip() {
+        let alphabet = Base64Alphabet::custom(
+            "zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA9876543210+/",
+            false,
+        )
+        .unwrap();
+        let encoded = Base64::encode("hello world");
+        let displayed = encoded.display_with(&alphabet).to_string();
+        assert!(!displayed.ends_with('='));
+        assert_eq!(
+            Base64::parse_with(&displayed, &alphabet).unwrap().decode(),
+            "hello world"
+        );
+    }
+}
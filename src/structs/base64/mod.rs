@@ -0,0 +1,7 @@
+mod base64_alphabet;
+mod base64_stream;
+mod base64_struct;
+
+pub use base64_alphabet::Base64Alphabet;
+pub use base64_stream::{Base64Decoder, Base64Encoder};
+pub use base64_struct::{Base64, Base64Display, MIME_LINE_WIDTH};
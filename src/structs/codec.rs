@@ -0,0 +1,163 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::structs::{Ascii85, Base32, Base58, Base64, CodecError, Hex, Z85};
+
+/********************************** CODEC ************************************/
+
+/// binary-to-text encoding: bytes are encoded to a value displayed as text,
+/// and text is parsed (`TryFrom<&str>`) to a value decoded to bytes
+pub trait Codec: Sized + fmt::Display + for<'a> TryFrom<&'a str, Error = CodecError> {
+    /// human readable name of the encoding
+    fn name() -> &'static str;
+
+    fn encode_bytes(input: &[u8]) -> Self;
+
+    fn decode_bytes(&self) -> Vec<u8>;
+
+    /// encode bytes straight to their textual representation
+    fn encode_to_string(input: &[u8]) -> String {
+        Self::encode_bytes(input).to_string()
+    }
+
+    /// decode a textual representation straight to bytes
+    fn decode_str(input: &str) -> Result<Vec<u8>, CodecError> {
+        Self::try_from(input).map(|codec| codec.decode_bytes())
+    }
+
+    /// check whether the input is a valid textual representation
+    fn validate(input: &str) -> Result<(), CodecError> {
+        Self::try_from(input).map(|_| ())
+    }
+}
+
+/// decode a text from the encoding `F` and encode it again to the encoding `T`
+pub fn transcode<F: Codec, T: Codec>(input: &str) -> Result<T, CodecError> {
+    F::decode_str(input).map(|bytes| T::encode_bytes(&bytes))
+}
+
+/***************************** IMPLEMENTATIONS ********************************/
+
+impl Codec for Hex {
+    fn name() -> &'static str {
+        "hex"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Hex::from(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        self.decode()
+    }
+}
+
+impl Codec for Base64 {
+    fn name() -> &'static str {
+        "base64"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Base64::encode_bytes(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        Base64::decode_bytes(self)
+    }
+}
+
+impl Codec for Base32 {
+    fn name() -> &'static str {
+        "base32"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Base32::encode_bytes(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        Base32::decode_bytes(self)
+    }
+}
+
+impl Codec for Base58 {
+    fn name() -> &'static str {
+        "base58"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Base58::encode_bytes(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        Base58::decode_bytes(self)
+    }
+}
+
+impl Codec for Ascii85 {
+    fn name() -> &'static str {
+        "ascii85"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Ascii85::encode_bytes(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        Ascii85::decode_bytes(self)
+    }
+}
+
+impl Codec for Z85 {
+    fn name() -> &'static str {
+        "z85"
+    }
+
+    fn encode_bytes(input: &[u8]) -> Self {
+        Z85::encode_bytes(input)
+    }
+
+    fn decode_bytes(&self) -> Vec<u8> {
+        Z85::decode_bytes(self)
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{transcode, Codec};
+    use crate::structs::{Ascii85, Base32, Base58, Base64, CodecError, Hex, Z85};
+
+    fn round_trip<C: Codec>() {
+        let input: Vec<u8> = [0, 0].iter().copied().chain(0..=255).collect();
+        let encoded = C::encode_to_string(&input);
+        assert_eq!(C::validate(&encoded), Ok(()), "{}", C::name());
+        assert_eq!(C::decode_str(&encoded), Ok(input), "{}", C::name());
+    }
+
+    #[test]
+    fn generic_round_trip() {
+        round_trip::<Hex>();
+        round_trip::<Base64>();
+        round_trip::<Base32>();
+        round_trip::<Base58>();
+        round_trip::<Ascii85>();
+        round_trip::<Z85>();
+    }
+
+    #[test]
+    fn generic_validation() {
+        assert_eq!(Hex::validate("aaa"), Err(CodecError::OddLength));
+        assert_eq!(Base64::validate("QQ="), Err(CodecError::BadPadding));
+    }
+
+    #[test]
+    fn transcode_hex_to_base64() {
+        let base_64: Base64 = transcode::<Hex, _>("68656c6c6f20776f726c6421").unwrap();
+        assert_eq!(base_64.to_string(), "aGVsbG8gd29ybGQh");
+
+        let hex: Result<Hex, _> = transcode::<Base64, _>("QQ=");
+        assert_eq!(hex, Err(CodecError::BadPadding));
+    }
+}
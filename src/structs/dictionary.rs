@@ -1,41 +1,121 @@
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 
-const DICTIONARY_PATH: &'static str = "data/dictionary/words_alpha.txt";
+use crate::structs::{ByteFrequency, CorpusError};
+
+/// the 100 most common english words in the Oxford English Corpus, by decreasing
+/// frequency, used by `Dictionary::english`
+const ENGLISH_WORDS: &str = include_str!("../../data/dictionary/english.txt");
+
+/// word list loaded by `Dictionary::new`
+const DICTIONARY_PATH: &str = "data/dictionary/words_alpha.txt";
 
 #[derive(Debug)]
 pub struct Dictionary {
-    words: Vec<String>,
+    /// lowercase words
+    words: HashSet<String>,
     letter_stats: Option<HashMap<char, u32>>,
 }
 
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
 impl Dictionary {
+    /// dictionary of the word list found at `data/dictionary/words_alpha.txt`
+    ///
+    /// panics if the file cannot be read
+    #[deprecated(note = "use `Dictionary::from_path`, or `Dictionary::english`")]
     pub fn new() -> Self {
-        let raw_content = fs::read_to_string(DICTIONARY_PATH).expect("Error loading dictionary");
-        Self {
-            words: raw_content
-                .replace("\r", "")
-                .split("\n")
-                .map(|w| w.to_owned())
-                .collect(),
-            letter_stats: None,
+        Self::from_path(DICTIONARY_PATH).expect("Error loading dictionary")
+    }
+
+    /// the 100 most common english words in the Oxford English Corpus, embedded in the
+    /// binary
+    ///
+    /// the words are lemmas: "be" is listed, but not "is" or "was"
+    pub fn english() -> Self {
+        Self::from_words(ENGLISH_WORDS.lines()).expect("the embedded dictionary is not empty")
+    }
+
+    /// dictionary of the given words, case insensitive
+    ///
+    /// fails with `CorpusError::NoWord` if there is no word at all
+    pub fn from_words<I, S>(words: I) -> Result<Self, CorpusError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words: HashSet<String> = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.is_empty() {
+            return Err(CorpusError::NoWord);
         }
+
+        Ok(Self {
+            words,
+            letter_stats: None,
+        })
+    }
+
+    /// dictionary of the words of a reader, one per line
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, CorpusError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Self::from_words(content.lines())
+    }
+
+    /// dictionary of the words of a file, one per line
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, CorpusError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// lowercase words of the input, separated by anything that is not alphanumerical
+    pub fn tokens(input: &str) -> impl Iterator<Item = String> + '_ {
+        input
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_lowercase())
+    }
+
+    /// number of words of the dictionary
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// whether the word belongs to the dictionary, case insensitive
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
     }
 
     /// Count the number of *words* (substring separated by anything that is not alphanumerical)
     pub fn hits(&self, input: &str) -> u32 {
-        input
-            .split(" ")
-            .collect::<Vec<&str>>()
-            .iter()
-            .filter(|&&w| w != "")
-            .fold(0u32, |hits, word| {
-                hits + if self.words.iter().any(|w| w == word) {
-                    1
-                } else {
-                    0
-                }
-            })
+        Self::tokens(input)
+            .filter(|token| self.words.contains(token))
+            .count() as u32
+    }
+
+    /// proportion of the words of the input found in the dictionary (0 without any word)
+    pub fn hit_ratio(&self, input: &str) -> f64 {
+        let (hits, total) = Self::tokens(input).fold((0usize, 0usize), |(hits, total), token| {
+            (hits + self.words.contains(&token) as usize, total + 1)
+        });
+        if total == 0 {
+            0.0
+        } else {
+            hits as f64 / total as f64
+        }
     }
 
     fn letter_counts(&mut self) {
@@ -64,4 +144,87 @@ impl Dictionary {
             }
         })
     }
+
+    /// byte frequencies of the words of the dictionary, separated by spaces
+    pub fn letter_frequency(&self) -> ByteFrequency {
+        let words: Vec<&str> = self.words.iter().map(|w| &w[..]).collect();
+        ByteFrequency::from_sample(words.join(" ").as_bytes())
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Dictionary;
+    use crate::structs::CorpusError;
+    use std::io::{Cursor, ErrorKind};
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_words(vec!["cooking", "like", "a", "pound", "of", "Bacon\r"]).unwrap()
+    }
+
+    #[test]
+    fn tokenization() {
+        assert_eq!(
+            Dictionary::tokens("Cooking MC's like a pound of bacon,\n").collect::<Vec<String>>(),
+            vec!["cooking", "mc", "s", "like", "a", "pound", "of", "bacon"]
+        );
+        assert_eq!(Dictionary::tokens(" -- ").count(), 0);
+    }
+
+    #[test]
+    fn hits() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.len(), 6);
+        assert!(dictionary.contains("BACON"));
+        assert_eq!(dictionary.hits("Cooking MC's like a pound of bacon"), 6);
+        assert_eq!(dictionary.hits("bacon,bacon;bacon"), 3);
+        assert_eq!(dictionary.hits(""), 0);
+    }
+
+    #[test]
+    fn hit_ratio() {
+        let dictionary = dictionary();
+        assert_eq!(
+            dictionary.hit_ratio("Cooking MC's like a pound of bacon"),
+            0.75
+        );
+        assert_eq!(dictionary.hit_ratio("xq zz"), 0.0);
+        assert_eq!(dictionary.hit_ratio("!!!"), 0.0);
+    }
+
+    #[test]
+    fn loading() {
+        let dictionary = Dictionary::from_reader(Cursor::new("Hello\r\n\nworld\n")).unwrap();
+        assert_eq!(dictionary.len(), 2);
+        assert!(dictionary.contains("hello"));
+
+        let dictionary = Dictionary::from_path("data/dictionary/english.txt").unwrap();
+        assert_eq!(dictionary.len(), Dictionary::english().len());
+
+        assert_eq!(
+            Dictionary::from_path("data/dictionary/missing.txt").unwrap_err(),
+            CorpusError::Io(ErrorKind::NotFound)
+        );
+        assert_eq!(
+            Dictionary::from_words(vec!["", " "]).unwrap_err(),
+            CorpusError::NoWord
+        );
+    }
+
+    #[test]
+    fn embedded_english() {
+        let dictionary = Dictionary::default();
+        assert_eq!(dictionary.len(), 100);
+        assert_eq!(
+            dictionary.hit_ratio("They say you can take the time to look back and think about it"),
+            1.0
+        );
+        assert_eq!(dictionary.hits("The weather was good for a walk"), 4);
+
+        // contraction fragments are not words
+        assert_eq!(dictionary.hits("we'll see, they're back, it's done"), 5);
+        assert!(!dictionary.contains("s"));
+    }
 }
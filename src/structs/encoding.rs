@@ -0,0 +1,240 @@
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+
+use crate::rank;
+use crate::structs::{is_text, Base64, Base64Alphabet, CodecError, Hex};
+
+/********************************* ENCODING **********************************/
+
+/// encodings recognized by `detect`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Base64UrlSafe,
+    /// printable ascii text, used as is
+    Ascii,
+    /// anything else, used as is
+    Binary,
+}
+
+/// an encoding along with the confidence (between 0 and 1) that the input uses it
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Detection {
+    pub encoding: Encoding,
+    pub confidence: f64,
+}
+
+impl Detection {
+    fn new(encoding: Encoding, confidence: f64) -> Self {
+        Self {
+            encoding,
+            confidence,
+        }
+    }
+}
+
+/// grows toward 1 with the length of the input: short inputs are ambiguous
+fn length_confidence(length: usize) -> f64 {
+    length as f64 / (length as f64 + 8.0)
+}
+
+/// whether the input looks like a single word: letters only, in lowercase, capitalized
+/// or in uppercase, unlike the mixed case of base64
+fn is_word(compact: &str) -> bool {
+    let rest = compact.get(1..).unwrap_or("");
+    compact.chars().all(|c| c.is_ascii_alphabetic())
+        && (rest.chars().all(|c| c.is_ascii_lowercase())
+            || rest.chars().all(|c| c.is_ascii_uppercase()))
+}
+
+/// the base64 alphabet (padded or not) able to parse the input, if any
+fn base64_alphabet(compact: &str, url_safe: bool) -> Option<Base64Alphabet> {
+    let alphabet = if url_safe {
+        Base64Alphabet::URL_SAFE
+    } else {
+        Base64Alphabet::STANDARD
+    };
+
+    [alphabet, alphabet.with_padding(false)]
+        .iter()
+        .copied()
+        .find(|alphabet| Base64::parse_with(compact, alphabet).is_ok())
+}
+
+/// every plausible encoding of the input, the most likely first
+pub fn candidates(input: &[u8]) -> Vec<Detection> {
+    let non_text = input.iter().filter(|b| !is_text(**b)).count();
+    if non_text > 0 {
+        let ratio = non_text as f64 / input.len() as f64;
+        return vec![Detection::new(Encoding::Binary, 0.5 + 0.5 * ratio)];
+    }
+
+    // only ascii from here, line breaks (of wrapped files) are meaningless
+    let text = String::from_utf8_lossy(input);
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let length = compact.len();
+    // spaces inside the content are typical of text, not of encoded data
+    let spaced = text.trim().contains([' ', '\t']);
+    let spacing_factor = if spaced { 0.5 } else { 1.0 };
+
+    // without padding, a length that is not a multiple of 4 needs an unpadded alphabet
+    let unaligned = !compact.ends_with('=') && !length.is_multiple_of(4);
+    let ascii_confidence = if spaced {
+        0.9
+    } else if is_word(&compact) {
+        0.8
+    } else if unaligned {
+        0.6
+    } else {
+        0.3
+    };
+    let mut detections = vec![Detection::new(Encoding::Ascii, ascii_confidence)];
+
+    if length > 0 && Hex::try_from(&compact[..]).is_ok() {
+        detections.push(Detection::new(
+            Encoding::Hex,
+            (0.5 + 0.5 * length_confidence(length)) * spacing_factor,
+        ));
+    }
+
+    for url_safe in [false, true].iter() {
+        if let Some(alphabet) = base64_alphabet(&compact, *url_safe).filter(|_| length > 0) {
+            // symbols specific to one alphabet make it much more likely
+            let specific = if *url_safe { "-_" } else { "+/" };
+            let bonus = if compact.contains(|c| specific.contains(c)) {
+                0.1
+            } else if alphabet.is_padded() && compact.ends_with('=') {
+                0.05
+            } else {
+                0.0
+            };
+            let encoding = if *url_safe {
+                Encoding::Base64UrlSafe
+            } else {
+                Encoding::Base64
+            };
+            detections.push(Detection::new(
+                encoding,
+                (0.4 + 0.5 * length_confidence(length) + bonus) * spacing_factor,
+            ));
+        }
+    }
+
+    rank::descending(&mut detections, |detection| detection.confidence);
+    detections
+}
+
+/// the most likely encoding of the input
+pub fn detect(input: &[u8]) -> Detection {
+    candidates(input)[0]
+}
+
+/// decode the input according to its most likely encoding
+pub fn decode_detected(input: &[u8]) -> Result<(Detection, Vec<u8>), CodecError> {
+    let detection = detect(input);
+    let compact: String = String::from_utf8_lossy(input)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    let bytes = match detection.encoding {
+        Encoding::Hex => Hex::try_from(&compact[..])?.into_bytes(),
+        Encoding::Base64 | Encoding::Base64UrlSafe => {
+            let alphabet = base64_alphabet(&compact, detection.encoding == Encoding::Base64UrlSafe)
+                .ok_or(CodecError::InvalidAlphabet)?;
+            Base64::parse_with(&compact, &alphabet)?.decode_bytes()
+        }
+        Encoding::Ascii | Encoding::Binary => input.to_vec(),
+    };
+    Ok((detection, bytes))
+}
+
+/// load a file and decode it according to its most likely encoding
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<(Detection, Vec<u8>), CodecError> {
+    decode_detected(&fs::read(path)?)
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{candidates, decode_detected, detect, load_file, Encoding};
+
+    #[test]
+    fn detect_hex() {
+        // challenge 3 of set 1
+        let input = b"1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
+        let detection = detect(input);
+        assert_eq!(detection.encoding, Encoding::Hex);
+        assert!(detection.confidence > 0.9);
+
+        // base64 is also plausible, but less likely
+        assert!(candidates(input)
+            .iter()
+            .any(|d| d.encoding == Encoding::Base64));
+    }
+
+    #[test]
+    fn detect_base64() {
+        let input = b"SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtl\nIGEgcG9pc29ub3VzIG11c2hyb29t\n";
+        assert_eq!(detect(input).encoding, Encoding::Base64);
+
+        let input = b"+/8=";
+        assert_eq!(detect(input).encoding, Encoding::Base64);
+    }
+
+    #[test]
+    fn detect_url_safe_base64() {
+        let input = b"eyJhbGciOiJIUzI1NiJ9-_8";
+        assert_eq!(detect(input).encoding, Encoding::Base64UrlSafe);
+    }
+
+    #[test]
+    fn detect_text() {
+        let detection = detect(b"Cooking MC's like a pound of bacon");
+        assert_eq!(detection.encoding, Encoding::Ascii);
+        assert!(detection.confidence > 0.8);
+    }
+
+    #[test]
+    fn detect_short_words() {
+        for word in ["test", "Hello", "WORLD", "cafe", "Crypto", "yes"].iter() {
+            assert_eq!(
+                detect(word.as_bytes()).encoding,
+                Encoding::Ascii,
+                "{}",
+                word
+            );
+        }
+        // not a word, but too short to be unpadded base64
+        assert_eq!(detect(b"x9k").encoding, Encoding::Ascii);
+    }
+
+    #[test]
+    fn detect_binary() {
+        assert_eq!(detect(&[0x00, 0xff, 0x41, 0x0e]).encoding, Encoding::Binary);
+    }
+
+    #[test]
+    fn decode_according_to_detection() {
+        let (detection, bytes) = decode_detected(b"68656c6c6f20776f726c6421\n").unwrap();
+        assert_eq!(detection.encoding, Encoding::Hex);
+        assert_eq!(bytes, b"hello world!".to_vec());
+
+        let (detection, bytes) = decode_detected(b"aGVsbG8gd29y\nbGQh").unwrap();
+        assert_eq!(detection.encoding, Encoding::Base64);
+        assert_eq!(bytes, b"hello world!".to_vec());
+
+        let (_, bytes) = decode_detected(b"hello world!").unwrap();
+        assert_eq!(bytes, b"hello world!".to_vec());
+    }
+
+    #[test]
+    fn load_challenge_file() {
+        let (detection, bytes) = load_file("data/challenges/4.txt").unwrap();
+        assert_eq!(detection.encoding, Encoding::Hex);
+        assert!(!bytes.is_empty());
+    }
+}
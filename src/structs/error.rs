@@ -0,0 +1,198 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/******************************* CODEC ERROR *********************************/
+
+/// error raised when parsing or decoding an encoded text (hexadecimal, base64, ...)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CodecError {
+    /// the input holds an odd number of hexadecimal symbols
+    OddLength,
+    /// the number of symbols cannot represent a whole number of bytes
+    InvalidLength(usize),
+    /// a character out of the alphabet, with its byte offset in the input
+    InvalidCharacter { character: char, offset: usize },
+    /// the padding is missing, too long or not allowed
+    BadPadding,
+    /// a group of symbols whose value does not fit in the bytes it encodes
+    GroupOverflow { offset: usize },
+    /// data found after the end of the encoded content (e.g. after the padding)
+    TrailingData { offset: usize },
+    /// the symbols given to build an alphabet are not valid
+    InvalidAlphabet,
+    /// the encoded content could not be read
+    Io(io::ErrorKind),
+}
+
+impl CodecError {
+    /// shift the offset of the error by `offset`, to locate it in a larger input
+    pub fn with_offset(self, offset: usize) -> Self {
+        match self {
+            Self::InvalidCharacter {
+                character,
+                offset: o,
+            } => Self::InvalidCharacter {
+                character,
+                offset: o + offset,
+            },
+            Self::GroupOverflow { offset: o } => Self::GroupOverflow { offset: o + offset },
+            Self::TrailingData { offset: o } => Self::TrailingData { offset: o + offset },
+            error => error,
+        }
+    }
+}
+
+/***************************** TRAITS *****************************************/
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OddLength => write!(f, "odd number of hexadecimal symbols"),
+            Self::InvalidLength(length) => write!(f, "invalid number of symbols: {}", length),
+            Self::InvalidCharacter { character, offset } => {
+                write!(f, "invalid character {:?} at offset {}", character, offset)
+            }
+            Self::BadPadding => write!(f, "invalid padding"),
+            Self::GroupOverflow { offset } => {
+                write!(f, "group starting at offset {} overflows", offset)
+            }
+            Self::TrailingData { offset } => write!(f, "trailing data at offset {}", offset),
+            Self::InvalidAlphabet => write!(f, "invalid alphabet"),
+            Self::Io(kind) => write!(f, "error reading input: {:?}", kind),
+        }
+    }
+}
+
+impl Error for CodecError {}
+
+impl From<io::Error> for CodecError {
+    fn from(error: io::Error) -> Self {
+        // errors raised by the streaming decoders carry a codec error
+        match error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<CodecError>())
+        {
+            Some(codec_error) => *codec_error,
+            None => Self::Io(error.kind()),
+        }
+    }
+}
+
+impl From<CodecError> for io::Error {
+    fn from(error: CodecError) -> Self {
+        match error {
+            CodecError::Io(kind) => io::Error::from(kind),
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+/******************************** XOR ERROR **********************************/
+
+/// error raised when XORing bytes with other bytes or with a key
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum XorError {
+    /// two inputs combined byte by byte do not have the same length
+    LengthMismatch { left: usize, right: usize },
+    /// a repeating key without any byte
+    EmptyKey,
+}
+
+impl fmt::Display for XorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { left, right } => {
+                write!(f, "length mismatch: {} and {} bytes", left, right)
+            }
+            Self::EmptyKey => write!(f, "empty key"),
+        }
+    }
+}
+
+impl Error for XorError {}
+
+/****************************** CORPUS ERROR *********************************/
+
+/// error raised when building a language model (e.g. a n-gram model or a dictionary)
+/// from a corpus
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CorpusError {
+    /// a n-gram model must count n-grams of at least one byte
+    InvalidOrder,
+    /// a dictionary must hold at least one word
+    NoWord,
+    /// the corpus could not be read
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for CorpusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidOrder => write!(f, "the order of a n-gram model must be positive"),
+            Self::NoWord => write!(f, "the dictionary holds no word"),
+            Self::Io(kind) => write!(f, "error reading corpus: {:?}", kind),
+        }
+    }
+}
+
+impl Error for CorpusError {}
+
+impl From<io::Error> for CorpusError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.kind())
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{CodecError, XorError};
+    use std::io;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            CodecError::InvalidCharacter {
+                character: 'y',
+                offset: 2
+            }
+            .to_string(),
+            "invalid character 'y' at offset 2"
+        );
+        assert_eq!(
+            CodecError::OddLength.to_string(),
+            "odd number of hexadecimal symbols"
+        );
+        assert_eq!(
+            XorError::LengthMismatch { left: 3, right: 2 }.to_string(),
+            "length mismatch: 3 and 2 bytes"
+        );
+    }
+
+    #[test]
+    fn shift_offset() {
+        assert_eq!(
+            CodecError::TrailingData { offset: 1 }.with_offset(4),
+            CodecError::TrailingData { offset: 5 }
+        );
+        assert_eq!(
+            CodecError::BadPadding.with_offset(4),
+            CodecError::BadPadding
+        );
+    }
+
+    #[test]
+    fn io_round_trip() {
+        let error = io::Error::from(CodecError::BadPadding);
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(CodecError::from(error), CodecError::BadPadding);
+
+        let error = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(
+            CodecError::from(error),
+            CodecError::Io(io::ErrorKind::NotFound)
+        );
+    }
+}
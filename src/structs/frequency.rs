@@ -0,0 +1,203 @@
+/// relative frequencies (in %) of letters in english text
+const ENGLISH_LETTERS: [(u8, f64); 26] = [
+    (b'a', 8.167),
+    (b'b', 1.492),
+    (b'c', 2.782),
+    (b'd', 4.253),
+    (b'e', 12.702),
+    (b'f', 2.228),
+    (b'g', 2.015),
+    (b'h', 6.094),
+    (b'i', 6.966),
+    (b'j', 0.153),
+    (b'k', 0.772),
+    (b'l', 4.025),
+    (b'm', 2.406),
+    (b'n', 6.749),
+    (b'o', 7.507),
+    (b'p', 1.929),
+    (b'q', 0.095),
+    (b'r', 5.987),
+    (b's', 6.327),
+    (b't', 9.056),
+    (b'u', 2.758),
+    (b'v', 0.978),
+    (b'w', 2.360),
+    (b'x', 0.150),
+    (b'y', 1.974),
+    (b'z', 0.074),
+];
+
+/// relative frequencies (in %) of the other usual symbols of english text
+const ENGLISH_SYMBOLS: [(u8, f64); 12] = [
+    (b' ', 21.0),
+    (b'.', 0.80),
+    (b',', 0.75),
+    (b'\n', 0.60),
+    (b'\'', 0.30),
+    (b'"', 0.30),
+    (b'-', 0.20),
+    (b'!', 0.10),
+    (b'?', 0.08),
+    (b';', 0.04),
+    (b':', 0.04),
+    (b'\t', 0.01),
+];
+
+/// relative frequency (in %) of each digit
+const DIGIT_FREQUENCY: f64 = 0.05;
+/// relative frequency (in %) of any other printable ascii symbol
+const PRINTABLE_FREQUENCY: f64 = 0.005;
+/// relative frequency (in %) of control & extended bytes, which are not expected in text
+const UNEXPECTED_FREQUENCY: f64 = 0.000_01;
+
+/****************************** BYTE FREQUENCY *******************************/
+
+/// expected frequency of each (case-folded) byte in a kind of text
+#[derive(Debug, PartialEq, Clone)]
+pub struct ByteFrequency {
+    frequencies: Vec<f64>,
+}
+
+impl ByteFrequency {
+    /// frequencies of english text, embedded in the binary
+    pub fn english() -> Self {
+        let mut weights: Vec<f64> = (0..=255u8)
+            .map(|byte| match byte {
+                b'0'..=b'9' => DIGIT_FREQUENCY,
+                b' '..=b'~' => PRINTABLE_FREQUENCY,
+                _ => UNEXPECTED_FREQUENCY,
+            })
+            .collect();
+
+        // letters make up what the other symbols leave of the text, the frequencies of
+        // `ENGLISH_LETTERS` being relative to letters only
+        let symbols: f64 = ENGLISH_SYMBOLS.iter().map(|(_, weight)| weight).sum();
+        let letter_share = 1.0 - symbols / 100.0;
+
+        // uppercase letters are folded into lowercase ones
+        for (byte, weight) in ENGLISH_LETTERS.iter() {
+            weights[*byte as usize] = weight * letter_share;
+            weights[byte.to_ascii_uppercase() as usize] = 0.0;
+        }
+        for (byte, weight) in ENGLISH_SYMBOLS.iter() {
+            weights[*byte as usize] = *weight;
+        }
+
+        Self::from_weights(&weights)
+    }
+
+    /// frequencies observed in a sample text
+    pub fn from_sample(sample: &[u8]) -> Self {
+        let mut weights = vec![UNEXPECTED_FREQUENCY; 256];
+        for byte in sample.iter() {
+            weights[byte.to_ascii_lowercase() as usize] += 1.0;
+        }
+        for byte in b'A'..=b'Z' {
+            weights[byte as usize] = 0.0;
+        }
+
+        Self::from_weights(&weights)
+    }
+
+    fn from_weights(weights: &[f64]) -> Self {
+        let total: f64 = weights.iter().sum();
+        Self {
+            frequencies: weights.iter().map(|w| w / total).collect(),
+        }
+    }
+
+    /// expected frequency of a byte, case insensitive
+    pub fn frequency(&self, byte: u8) -> f64 {
+        self.frequencies[byte.to_ascii_lowercase() as usize]
+    }
+
+    /// chi-squared statistic between the observed & expected byte counts, divided by
+    /// the input length: the lower, the closer the input is to the expected frequencies
+    pub fn chi_squared(&self, input: &[u8]) -> f64 {
+        if input.is_empty() {
+            return 0.0;
+        }
+
+        let mut counts = [0usize; 256];
+        for byte in input.iter() {
+            counts[byte.to_ascii_lowercase() as usize] += 1;
+        }
+
+        let length = input.len() as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .zip(self.frequencies.iter())
+            .filter(|(_, frequency)| **frequency > 0.0)
+            .map(|(count, frequency)| {
+                let expected = frequency * length;
+                (*count as f64 - expected).powi(2) / expected
+            })
+            .sum();
+
+        chi_squared / length
+    }
+
+    /// mean natural logarithm of the probability of each byte of the input: the higher,
+    /// the more likely the input is to follow the expected frequencies
+    pub fn log_likelihood(&self, input: &[u8]) -> f64 {
+        if input.is_empty() {
+            return 0.0;
+        }
+
+        input
+            .iter()
+            .map(|byte| self.frequency(*byte).ln())
+            .sum::<f64>()
+            / input.len() as f64
+    }
+}
+
+impl Default for ByteFrequency {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::ByteFrequency;
+    use crate::testing::{NOISE, TEXT};
+
+    #[test]
+    fn frequencies_sum_to_one() {
+        let english = ByteFrequency::english();
+        let total: f64 = (0..=255u8)
+            .filter(|b| !b.is_ascii_uppercase())
+            .map(|b| english.frequency(b))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(english.frequency(b'E'), english.frequency(b'e'));
+    }
+
+    #[test]
+    fn english_is_closer_than_noise() {
+        let english = ByteFrequency::english();
+        assert!(english.chi_squared(TEXT) < english.chi_squared(NOISE));
+        assert!(english.log_likelihood(TEXT) > english.log_likelihood(NOISE));
+    }
+
+    #[test]
+    fn scores_are_length_normalized() {
+        let english = ByteFrequency::english();
+        let text = b"the quick brown fox jumps over the lazy dog ";
+        let twice = [&text[..], &text[..]].concat();
+
+        assert!((english.chi_squared(text) - english.chi_squared(&twice)).abs() < 1e-9);
+        assert!((english.log_likelihood(text) - english.log_likelihood(&twice)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sample_frequencies() {
+        let sample = ByteFrequency::from_sample(b"aab");
+        assert!(sample.frequency(b'a') > sample.frequency(b'b'));
+        assert!(sample.log_likelihood(b"AAB") > sample.log_likelihood(b"bba"));
+    }
+}
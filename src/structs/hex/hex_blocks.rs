@@ -0,0 +1,172 @@
+use super::hex_struct::Hex;
+use super::hex_symbol::HexSymbol;
+
+/// panics if `size` is 0, blocks being made of bytes
+fn assert_block_size(size: usize) {
+    assert!(size > 0, "blocks must hold at least one byte");
+}
+
+/********************************** BLOCKS ***********************************/
+
+impl Hex {
+    /// split the hexadecimal into blocks of `size` bytes, the last one being shorter if
+    /// the length is not a multiple of `size`
+    ///
+    /// panics if `size` is 0
+    pub fn blocks(&self, size: usize) -> Vec<Hex> {
+        assert_block_size(size);
+        self.chunks(size).map(Hex::new).collect()
+    }
+
+    /// split the hexadecimal into blocks of `size` bytes, the last one being completed
+    /// with `padding` bytes if the length is not a multiple of `size`
+    ///
+    /// panics if `size` is 0
+    pub fn blocks_padded(&self, size: usize, padding: u8) -> Vec<Hex> {
+        let mut blocks = self.blocks(size);
+        if let Some(last) = blocks.last_mut() {
+            let missing = size - last.len();
+            let symbols = [&last[..], &vec![HexSymbol::new(padding); missing][..]].concat();
+            *last = Hex::new(&symbols);
+        }
+        blocks
+    }
+
+    /// number of blocks of `size` bytes, the last one being possibly incomplete
+    ///
+    /// panics if `size` is 0
+    pub fn block_count(&self, size: usize) -> usize {
+        assert_block_size(size);
+        self.len().div_ceil(size)
+    }
+
+    /// the `index`-th block of `size` bytes (possibly incomplete if it is the last one),
+    /// or `None` if there is no such block
+    ///
+    /// panics if `size` is 0
+    pub fn block(&self, size: usize, index: usize) -> Option<Hex> {
+        assert_block_size(size);
+        self.chunks(size).nth(index).map(Hex::new)
+    }
+
+    /// overwrite the `index`-th block of `size` bytes with `block`
+    ///
+    /// panics if `size` is 0, if there is no such block, or if `block` does not have the
+    /// same length as the block it replaces
+    pub fn replace_block(&mut self, size: usize, index: usize, block: &Hex) {
+        assert_block_size(size);
+        let start = index
+            .checked_mul(size)
+            .filter(|start| *start < self.len())
+            .unwrap_or_else(|| panic!("there is no block {}", index));
+        let end = start.saturating_add(size).min(self.len());
+        assert_eq!(end - start, block.len(), "the block length does not match");
+
+        *self = Hex::new(&[&self[..start], &block[..], &self[end..]].concat());
+    }
+
+    /// split the hexadecimal into `size` columns, the i-th one holding the i-th byte of
+    /// every block of `size` bytes (e.g. the bytes encrypted with the same byte of a
+    /// repeating key), see `Hex::interleave` for the opposite
+    ///
+    /// panics if `size` is 0
+    pub fn transpose(&self, size: usize) -> Vec<Hex> {
+        assert_block_size(size);
+        (0..size.min(self.len()))
+            .map(|column| {
+                let symbols: Vec<HexSymbol> =
+                    self.iter().skip(column).step_by(size).copied().collect();
+                Hex::new(&symbols)
+            })
+            .collect()
+    }
+
+    /// join columns back into a single hexadecimal, taking one byte of each column in
+    /// turn, opposite of `Hex::transpose`
+    ///
+    /// columns shorter than the first one end the output early
+    pub fn interleave(columns: &[Hex]) -> Hex {
+        let rows = columns.first().map_or(0, |column| column.len());
+        let mut symbols = Vec::with_capacity(rows * columns.len());
+        'rows: for row in 0..rows {
+            for column in columns.iter() {
+                match column.get(row) {
+                    Some(symbol) => symbols.push(*symbol),
+                    None => break 'rows,
+                }
+            }
+        }
+        Hex::new(&symbols)
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Hex;
+
+    #[test]
+    fn blocks() {
+        let hex = Hex::encode("YELLOW SUBMARINE!");
+        assert_eq!(
+            hex.blocks(8),
+            vec![
+                Hex::encode("YELLOW S"),
+                Hex::encode("UBMARINE"),
+                Hex::encode("!")
+            ]
+        );
+        assert_eq!(hex.block_count(8), 3);
+        assert_eq!(
+            hex.blocks_padded(8, 4)[2],
+            Hex::encode("!\x04\x04\x04\x04\x04\x04\x04")
+        );
+        assert_eq!(Hex::encode("").blocks(8), Vec::<Hex>::new());
+        assert_eq!(Hex::encode("").blocks_padded(8, 4), Vec::<Hex>::new());
+    }
+
+    #[test]
+    fn single_block() {
+        let mut hex = Hex::encode("YELLOW SUBMARINE!");
+        assert_eq!(hex.block(8, 1), Some(Hex::encode("UBMARINE")));
+        assert_eq!(hex.block(8, 2), Some(Hex::encode("!")));
+        assert_eq!(hex.block(8, 3), None);
+
+        hex.replace_block(8, 1, &Hex::encode("ubmarine"));
+        hex.replace_block(8, 2, &Hex::encode("?"));
+        assert_eq!(hex, Hex::encode("YELLOW Submarine?"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_block_of_another_length() {
+        Hex::encode("YELLOW SUBMARINE").replace_block(8, 1, &Hex::encode("sub"));
+    }
+
+    #[test]
+    #[should_panic(expected = "blocks must hold at least one byte")]
+    fn replace_empty_block() {
+        Hex::encode("YELLOW SUBMARINE").replace_block(0, 0, &Hex::encode(""));
+    }
+
+    #[test]
+    #[should_panic(expected = "there is no block")]
+    fn replace_block_far_away() {
+        Hex::encode("YELLOW SUBMARINE").replace_block(8, usize::MAX, &Hex::encode("sub"));
+    }
+
+    #[test]
+    fn transpose_and_interleave() {
+        let hex = Hex::encode("abcabcab");
+        let columns = hex.transpose(3);
+        assert_eq!(
+            columns,
+            vec![Hex::encode("aaa"), Hex::encode("bbb"), Hex::encode("cc")]
+        );
+        assert_eq!(Hex::interleave(&columns), hex);
+
+        assert_eq!(Hex::encode("ab").transpose(3).len(), 2);
+        assert_eq!(Hex::interleave(&[]), Hex::encode(""));
+    }
+}
@@ -0,0 +1,256 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::hex_struct::Hex;
+use super::hex_symbol::HexSymbol;
+use crate::structs::{is_printable_ascii, CodecError};
+
+/// separator between the bytes and the ascii gutter of a row
+const GUTTER_SEPARATOR: &str = "  ";
+
+/********************************* HEX DUMP **********************************/
+
+/// xxd-like view of an hexadecimal, see `Hex::dump`
+///
+/// ```text
+/// 00000000: 4865 6c6c 6f20 776f 726c 6421 0a         Hello world!.
+/// ```
+pub struct HexDump<'a> {
+    hex: &'a Hex,
+    width: usize,
+    group: usize,
+    block_size: Option<usize>,
+}
+
+impl<'a> HexDump<'a> {
+    pub(super) fn new(hex: &'a Hex) -> Self {
+        Self {
+            hex,
+            width: 16,
+            group: 2,
+            block_size: None,
+        }
+    }
+
+    /// number of bytes per row (16 by default)
+    pub fn width(self, width: usize) -> Self {
+        Self {
+            width: width.max(1),
+            ..self
+        }
+    }
+
+    /// number of bytes per space-separated group (2 by default, 0 for no grouping)
+    pub fn group(self, group: usize) -> Self {
+        Self { group, ..self }
+    }
+
+    /// start each block of `block_size` bytes (e.g. a cipher block) on its own row,
+    /// with a separator line between blocks
+    pub fn blocks(self, block_size: usize) -> Self {
+        Self {
+            block_size: Some(block_size.max(1)),
+            ..self
+        }
+    }
+
+    /// offset & content of each row
+    fn rows(&self) -> Vec<(usize, &'a [HexSymbol])> {
+        let symbols: &'a [HexSymbol] = self.hex;
+        let mut rows = Vec::new();
+        let mut start = 0;
+        while start < symbols.len() {
+            let mut end = (start + self.width).min(symbols.len());
+            if let Some(block_size) = self.block_size {
+                end = end.min((start / block_size + 1) * block_size);
+            }
+            rows.push((start, &symbols[start..end]));
+            start = end;
+        }
+        rows
+    }
+
+    fn is_group_start(&self, i: usize) -> bool {
+        i > 0 && self.group > 0 && i.is_multiple_of(self.group)
+    }
+
+    /// width of the bytes column of a full row
+    fn bytes_width(&self) -> usize {
+        (0..self.width).filter(|i| self.is_group_start(*i)).count() + self.width * 2
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes_width = self.bytes_width();
+
+        for (offset, row) in self.rows() {
+            if let Some(block_size) = self.block_size {
+                if offset > 0 && offset.is_multiple_of(block_size) {
+                    writeln!(
+                        f,
+                        "{}",
+                        "-".repeat(10 + bytes_width + GUTTER_SEPARATOR.len() + self.width)
+                    )?;
+                }
+            }
+
+            let mut bytes = String::with_capacity(bytes_width);
+            for (i, symbol) in row.iter().enumerate() {
+                if self.is_group_start(i) {
+                    bytes.push(' ');
+                }
+                bytes.push_str(&symbol.to_string());
+            }
+
+            let gutter: String = row
+                .iter()
+                .map(|symbol| match symbol.decode() {
+                    byte if is_printable_ascii(byte) => byte as char,
+                    _ => '.',
+                })
+                .collect();
+
+            writeln!(
+                f,
+                "{:08x}: {:width$}{}{}",
+                offset,
+                bytes,
+                GUTTER_SEPARATOR,
+                gutter,
+                width = bytes_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/***************************** PARSING ****************************************/
+
+impl Hex {
+    /// xxd-like view of the hexadecimal, to be configured and displayed
+    pub fn dump(&self) -> HexDump<'_> {
+        HexDump::new(self)
+    }
+
+    /// read back a dump such as the ones displayed by `Hex::dump` or `xxd`
+    ///
+    /// offsets are ignored, rows being read in order, as well as block separators
+    pub fn from_dump(dump: &str) -> Result<Self, CodecError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut line_start = 0;
+
+        for line in dump.split_inclusive('\n') {
+            let start = line_start;
+            line_start += line.len();
+
+            let content = line.trim_end();
+            if content.is_empty() || content.starts_with('-') {
+                continue;
+            }
+
+            // skip the offset, then stop at the gutter
+            let bytes_start = match content.find(':') {
+                Some(i) if content[..i].chars().all(|c| c.is_ascii_hexdigit()) => i + 1,
+                _ => 0,
+            };
+            let section = content[bytes_start..].trim_start();
+            let section_start = start + content.len() - section.len();
+            let section = match section.find(GUTTER_SEPARATOR) {
+                Some(end) => &section[..end],
+                None => section,
+            };
+
+            if let Some((offset, character)) = section
+                .char_indices()
+                .find(|(_, c)| *c != ' ' && !c.is_ascii_hexdigit())
+            {
+                return Err(CodecError::InvalidCharacter {
+                    character,
+                    offset: section_start + offset,
+                });
+            }
+
+            let compact: String = section.chars().filter(|c| *c != ' ').collect();
+            bytes.extend(Hex::try_from(&compact[..])?.into_bytes());
+        }
+
+        Ok(Hex::from(bytes))
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::Hex;
+    use crate::structs::CodecError;
+
+    #[test]
+    fn dump_like_xxd() {
+        let hex = Hex::encode("Hello world!\n");
+        assert_eq!(
+            hex.dump().to_string(),
+            "00000000: 4865 6c6c 6f20 776f 726c 6421 0a         Hello world!.\n"
+        );
+    }
+
+    #[test]
+    fn dump_rows_and_groups() {
+        let hex = Hex::from((0u8..20).collect::<Vec<u8>>());
+        assert_eq!(
+            hex.dump().width(8).group(4).to_string(),
+            "00000000: 00010203 04050607  ........\n\
+             00000008: 08090a0b 0c0d0e0f  ........\n\
+             00000010: 10111213           ....\n"
+        );
+        assert_eq!(
+            Hex::encode("AB\u{7f}").dump().group(0).to_string(),
+            format!("00000000: 41427f{}  AB.\n", " ".repeat(26))
+        );
+    }
+
+    #[test]
+    fn dump_blocks() {
+        let hex = Hex::encode("YELLOW SUBMARINE");
+        assert_eq!(
+            hex.dump().width(6).blocks(4).to_string(),
+            "00000000: 5945 4c4c       YELL\n\
+             --------------------------------\n\
+             00000004: 4f57 2053       OW S\n\
+             --------------------------------\n\
+             00000008: 5542 4d41       UBMA\n\
+             --------------------------------\n\
+             0000000c: 5249 4e45       RINE\n"
+        );
+    }
+
+    #[test]
+    fn dump_and_parse() {
+        let hex = Hex::from((0u8..=255).collect::<Vec<u8>>());
+        for dump in [
+            hex.dump().to_string(),
+            hex.dump().width(7).group(3).blocks(16).to_string(),
+            hex.dump().group(0).to_string(),
+        ]
+        .iter()
+        {
+            assert_eq!(Hex::from_dump(dump), Ok(hex.clone()));
+        }
+    }
+
+    #[test]
+    fn parse_illegal_dump() {
+        assert_eq!(
+            Hex::from_dump("00000000: 4865 6x6c  He.l\n"),
+            Err(CodecError::InvalidCharacter {
+                character: 'x',
+                offset: 16
+            })
+        );
+        assert_eq!(
+            Hex::from_dump("00000000: 4865 6  He\n"),
+            Err(CodecError::OddLength)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::io::{self, Read, Write};
+
+use super::hex_symbol::HexSymbol;
+use crate::structs::CodecError;
+
+/// size of the chunks read from, or written to, the inner stream
+const BUFFER_SIZE: usize = 4096;
+
+/******************************** HEX ENCODER ********************************/
+
+/// writer encoding everything written to it as lowercase hexadecimal into the inner writer
+pub struct HexEncoder<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> HexEncoder<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HexEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // never encode more than BUFFER_SIZE bytes at once to keep memory bounded
+        let buf = &buf[..buf.len().min(BUFFER_SIZE)];
+
+        let mut output = Vec::with_capacity(buf.len() * 2);
+        for byte in buf.iter() {
+            output.push(HexSymbol::encode_partial(byte >> 4) as u8);
+            output.push(HexSymbol::encode_partial(byte & 0b0000_1111) as u8);
+        }
+
+        self.inner.write_all(&output)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/******************************** HEX DECODER ********************************/
+
+/// reader decoding the hexadecimal read from the inner reader, ignoring whitespace
+pub struct HexDecoder<R: Read> {
+    inner: R,
+    // high nibble waiting for its low nibble
+    high: Option<u8>,
+    // number of bytes read from the inner reader
+    offset: usize,
+    // bytes decoded but not yet read
+    decoded: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+impl<R: Read> HexDecoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            high: None,
+            offset: 0,
+            decoded: Vec::with_capacity(BUFFER_SIZE / 2),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode_symbol(&mut self, symbol: u8) -> Result<(), CodecError> {
+        let c = symbol as char;
+        self.offset += 1;
+        if c.is_ascii_whitespace() {
+            return Ok(());
+        }
+
+        let nibble = HexSymbol::decode_partial(c).map_err(|e| e.with_offset(self.offset - 1))?;
+        match self.high.take() {
+            Some(high) => self.decoded.push((high << 4) + nibble),
+            None => self.high = Some(nibble),
+        }
+        Ok(())
+    }
+
+    /// read the inner reader until some bytes are decoded or the end is reached
+    fn fill(&mut self) -> io::Result<()> {
+        self.decoded.clear();
+        self.position = 0;
+
+        let mut raw = [0u8; BUFFER_SIZE];
+        while self.decoded.is_empty() && !self.eof {
+            let n = self.inner.read(&mut raw)?;
+            if n == 0 {
+                self.eof = true;
+                // odd number of symbols
+                if self.high.is_some() {
+                    return Err(CodecError::OddLength.into());
+                }
+            }
+            for symbol in raw[..n].iter() {
+                self.decode_symbol(*symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for HexDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.decoded.len() {
+            self.fill()?;
+        }
+
+        let available = &self.decoded[self.position..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{HexDecoder, HexEncoder};
+    use crate::structs::{CodecError, Hex};
+    use crate::testing::read_error;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn encoder_matches_hex() {
+        let input: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+
+        let mut encoder = HexEncoder::new(Vec::new());
+        for part in input.chunks(7) {
+            encoder.write_all(part).unwrap();
+        }
+        let encoded = String::from_utf8(encoder.into_inner()).unwrap();
+        assert_eq!(encoded, Hex::from(input).to_string());
+    }
+
+    #[test]
+    fn decoder_matches_hex() {
+        let input: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let encoded = Hex::from(&input[..]).to_string().to_uppercase() + "\n";
+
+        let mut decoded = Vec::new();
+        HexDecoder::new(encoded.as_bytes())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, input);
+    }
+
+    #[test]
+    fn decoder_illegal_input() {
+        assert_eq!(
+            read_error(HexDecoder::new(&b"aaa"[..])),
+            CodecError::OddLength
+        );
+        assert_eq!(
+            read_error(HexDecoder::new(&b"aa\ny2"[..])),
+            CodecError::InvalidCharacter {
+                character: 'y',
+                offset: 3
+            }
+        );
+    }
+}
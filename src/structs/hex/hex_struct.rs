@@ -1,10 +1,16 @@
 use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Deref;
+use std::string::FromUtf8Error;
 
 use super::hex_symbol::HexSymbol;
+use crate::structs::{rolling_xor_in_place, xor_in_place, CodecError, XorError};
 
-const PARSING_ERROR: &str = "Error parsing Hexadecimal";
+// `Hex::as_bytes` views symbols as bytes, which must then share their layout
+const _: () = assert!(
+    std::mem::size_of::<HexSymbol>() == std::mem::size_of::<u8>()
+        && std::mem::align_of::<HexSymbol>() == std::mem::align_of::<u8>()
+);
 
 /********************************** HEX **************************************/
 
@@ -16,40 +22,90 @@ impl Hex {
         Self(symbols.to_vec())
     }
 
-    /// encode an ascii encoded string to an hexadecimal type
+    /// encode the bytes of an utf-8 string to an hexadecimal type
     pub fn encode(input: &str) -> Self {
-        Self(input.chars().map(|c| HexSymbol::new(c as u8)).collect())
+        Self::from(input.as_bytes())
     }
 
-    /// decode the hexadecimal to an ascii encoded string
-    pub fn decode(&self) -> String {
-        self.iter().map(|h| h.decode() as char).collect()
+    /// decode the hexadecimal to the raw bytes it holds
+    pub fn decode(&self) -> Vec<u8> {
+        self.iter().map(|h| h.decode()).collect()
     }
 
+    /// decode the hexadecimal to a string, replacing invalid utf-8 sequences
+    pub fn decode_lossy(&self) -> String {
+        String::from_utf8_lossy(self.as_bytes()).into_owned()
+    }
+
+    /// decode the hexadecimal to a string, failing on invalid utf-8 sequences
+    pub fn decode_utf8(&self) -> Result<String, FromUtf8Error> {
+        String::from_utf8(self.decode())
+    }
+
+    /// view the hexadecimal as a slice of raw bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `HexSymbol` is `repr(transparent)` over `u8`, so a slice of
+        // symbols has exactly the layout of a slice of bytes
+        unsafe { std::slice::from_raw_parts(self.0.as_ptr() as *const u8, self.0.len()) }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.decode()
+    }
+
+    /// XOR with another hexadecimal of the same length
+    pub fn xor(&self, other: &Self) -> Result<Self, XorError> {
+        let mut bytes = self.decode();
+        xor_in_place(&mut bytes, other.as_bytes())?;
+        Ok(Self::from(bytes))
+    }
+
+    /// XOR with a key repeated as many times as needed, failing if the key is empty
+    pub fn try_rolling_xor(&self, key: &Self) -> Result<Self, XorError> {
+        let mut bytes = self.decode();
+        rolling_xor_in_place(&mut bytes, key.as_bytes())?;
+        Ok(Self::from(bytes))
+    }
+
+    /// XOR with a key repeated as many times as needed
+    ///
+    /// panics if the key is empty, see `Hex::try_rolling_xor`
     pub fn rolling_xor(&self, key: &Self) -> Self {
-        Self(self.0.chunks(key.0.len()).fold(Vec::new(), |tab, chunk| {
-            [
-                tab,
-                chunk
-                    .iter()
-                    .zip(key.0.iter())
-                    .map(|(self_symbol, key_symbol)| *self_symbol ^ *key_symbol)
-                    .collect::<Vec<HexSymbol>>(),
-            ]
-            .concat()
-        }))
+        self.try_rolling_xor(key)
+            .expect("the key must hold at least one byte")
     }
 
-    pub fn hamming(&self, other: &Self) -> u32 {
-        // panic if both Hex are not the same length
-        assert_eq!(self.0.len(), other.0.len());
+    /// number of differing bits with another hexadecimal of the same length
+    pub fn try_hamming(&self, other: &Self) -> Result<u32, XorError> {
+        if self.len() != other.len() {
+            return Err(XorError::LengthMismatch {
+                left: self.len(),
+                right: other.len(),
+            });
+        }
 
-        self.0.iter().zip((*other).iter()).fold(
+        Ok(self.0.iter().zip((*other).iter()).fold(
             0u32,
             |hamming_distance, (self_symbol, other_symbol)| {
                 hamming_distance + self_symbol.hamming(other_symbol)
             },
-        )
+        ))
+    }
+
+    /// number of differing bits with another hexadecimal of the same length
+    ///
+    /// panics if both do not have the same length, see `Hex::try_hamming`
+    pub fn hamming(&self, other: &Self) -> u32 {
+        self.try_hamming(other)
+            .expect("both hexadecimals must have the same length")
+    }
+
+    /// hamming distance between the common part of both hexadecimals, each extra byte
+    /// of the longest one counting as 8 differing bits
+    pub fn edit_distance(&self, other: &Self) -> u32 {
+        let common = self.len().min(other.len());
+        let extra = self.len().max(other.len()) - common;
+        Self::new(&self[..common]).hamming(&Self::new(&other[..common])) + 8 * extra as u32
     }
 }
 
@@ -63,28 +119,52 @@ impl Deref for Hex {
     }
 }
 
+impl AsRef<[u8]> for Hex {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl From<&[u8]> for Hex {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.iter().map(|b| HexSymbol::new(*b)).collect())
+    }
+}
+
+impl From<Vec<u8>> for Hex {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from(&bytes[..])
+    }
+}
+
+impl From<Hex> for Vec<u8> {
+    fn from(hex: Hex) -> Self {
+        hex.into_bytes()
+    }
+}
+
 impl TryFrom<&str> for Hex {
-    type Error = &'static str;
+    type Error = CodecError;
 
     fn try_from(hex: &str) -> Result<Self, Self::Error> {
-        let length = hex.len();
-        if length
-            != hex
-                .to_lowercase()
-                .chars()
-                .filter(|c| "1234567890abcdef".contains(*c))
-                .count()
-            || length % 2 != 0
+        // report the first character out of the alphabet before any length issue
+        if let Some((offset, character)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit())
         {
-            return Err(PARSING_ERROR);
+            return Err(CodecError::InvalidCharacter { character, offset });
+        }
+        if !hex.len().is_multiple_of(2) {
+            return Err(CodecError::OddLength);
         }
 
-        let symbols: Vec<HexSymbol> = hex
-            .to_lowercase()
+        let symbols = hex
             .as_bytes()
             .chunks(2)
-            .map(|tuple| HexSymbol::from((tuple[0] as char, tuple[1] as char)))
-            .collect();
+            .enumerate()
+            .map(|(i, tuple)| {
+                HexSymbol::try_from((tuple[0] as char, tuple[1] as char))
+                    .map_err(|e| e.with_offset(2 * i))
+            })
+            .collect::<Result<Vec<HexSymbol>, CodecError>>()?;
 
         Ok(Self(symbols))
     }
@@ -109,15 +189,8 @@ impl std::ops::BitXor for Hex {
     // rhs is the "right-hand side" of the expression `a ^ b`
     fn bitxor(self, rhs: Self) -> Self::Output {
         // panic if both vector do not have same size
-        assert_eq!(self.0.len(), rhs.0.len());
-
-        Self(
-            self.0
-                .iter()
-                .zip(rhs.0.iter())
-                .map(|(self_symbol, rhs_symbol)| *self_symbol ^ *rhs_symbol)
-                .collect::<Vec<HexSymbol>>(),
-        )
+        self.xor(&rhs)
+            .expect("both hexadecimals must have the same length")
     }
 }
 
@@ -127,17 +200,30 @@ impl std::ops::BitXor for Hex {
 mod test {
     use super::Hex;
     use super::HexSymbol;
-    use super::PARSING_ERROR;
+    use super::{CodecError, XorError};
     use std::convert::TryFrom;
 
     #[test]
     fn wrong_input_odd_length() {
-        assert_eq!(Hex::try_from("aaa"), Err(PARSING_ERROR));
+        assert_eq!(Hex::try_from("aaa"), Err(CodecError::OddLength));
     }
 
     #[test]
     fn wrong_input_illegal_char() {
-        assert_eq!(Hex::try_from("aay2"), Err(PARSING_ERROR));
+        assert_eq!(
+            Hex::try_from("aay2"),
+            Err(CodecError::InvalidCharacter {
+                character: 'y',
+                offset: 2
+            })
+        );
+        assert_eq!(
+            Hex::try_from("aaé2"),
+            Err(CodecError::InvalidCharacter {
+                character: 'é',
+                offset: 2
+            })
+        );
     }
 
     #[test]
@@ -159,7 +245,7 @@ mod test {
     #[test]
     fn encode_input() {
         let input = "hello world!";
-        let encoded = Hex::encode(&input);
+        let encoded = Hex::encode(input);
         assert_eq!(encoded.to_string(), "68656c6c6f20776f726c6421".to_string());
     }
 
@@ -167,7 +253,52 @@ mod test {
     fn decode_input() {
         let input = "68656c6c6f20776f726c6421";
         let decoded = Hex::try_from(input).unwrap().decode();
-        assert_eq!(decoded, "hello world!".to_string())
+        assert_eq!(decoded, b"hello world!".to_vec())
+    }
+
+    #[test]
+    fn decode_input_as_string() {
+        let input = "68656c6c6f20776f726c6421";
+        let hex = Hex::try_from(input).unwrap();
+        assert_eq!(hex.decode_lossy(), "hello world!".to_string());
+        assert_eq!(hex.decode_utf8(), Ok("hello world!".to_string()));
+    }
+
+    #[test]
+    fn decode_invalid_utf8() {
+        let hex = Hex::from(vec![0x68, 0xff, 0x69]);
+        assert!(hex.decode_utf8().is_err());
+        assert_eq!(hex.decode_lossy(), "h\u{fffd}i".to_string());
+    }
+
+    #[test]
+    fn encode_non_ascii_input() {
+        let encoded = Hex::encode("é");
+        assert_eq!(encoded.to_string(), "c3a9".to_string());
+        assert_eq!(encoded.decode_utf8(), Ok("é".to_string()));
+    }
+
+    #[test]
+    fn from_bytes() {
+        let bytes: &[u8] = &[0x00, 0x7f, 0x80, 0xff];
+        let hex = Hex::from(bytes);
+        assert_eq!(hex.to_string(), "007f80ff".to_string());
+        assert_eq!(hex.as_ref(), bytes);
+        assert_eq!(hex, Hex::from(bytes.to_vec()));
+        assert_eq!(hex.into_bytes(), bytes.to_vec());
+    }
+
+    #[test]
+    fn bytes_view() {
+        // the view of `as_bytes` relies on symbols being laid out as bytes
+        assert_eq!(std::mem::size_of::<HexSymbol>(), 1);
+        assert_eq!(std::mem::align_of::<HexSymbol>(), 1);
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let hex = Hex::from(bytes.clone());
+        assert_eq!(hex.as_bytes(), &bytes[..]);
+        assert_eq!(hex.as_bytes().as_ptr(), hex.as_ptr() as *const u8);
+        assert_eq!(hex.as_bytes()[1..3], hex.decode()[1..3]);
     }
 
     #[test]
@@ -177,4 +308,45 @@ mod test {
 
         assert_eq!(test.hamming(&wokka), 37);
     }
+
+    #[test]
+    fn fallible_operations() {
+        let test = Hex::encode("this is a test");
+        let short = Hex::encode("this");
+
+        assert_eq!(
+            test.xor(&short),
+            Err(XorError::LengthMismatch { left: 14, right: 4 })
+        );
+        assert_eq!(test.xor(&test), Ok(Hex::from(vec![0; 14])));
+        assert_eq!(
+            test.try_hamming(&short),
+            Err(XorError::LengthMismatch { left: 14, right: 4 })
+        );
+        assert_eq!(
+            test.try_rolling_xor(&Hex::encode("")),
+            Err(XorError::EmptyKey)
+        );
+    }
+
+    #[test]
+    fn edit_distance() {
+        let test = Hex::encode("this is a test");
+        let wokka = Hex::encode("wokka wokka!!!");
+
+        assert_eq!(test.edit_distance(&wokka), 37);
+        assert_eq!(Hex::encode("this").edit_distance(&test), 8 * 10);
+        assert_eq!(test.edit_distance(&Hex::encode("")), 8 * 14);
+    }
+
+    #[test]
+    fn rolling_xor() {
+        let hex = Hex::encode("Burning 'em, if you ain't quick and nimble");
+        let key = Hex::encode("ICE");
+        assert_eq!(
+            hex.rolling_xor(&key).to_string(),
+            "0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20"
+        );
+        assert_eq!(hex.rolling_xor(&key).rolling_xor(&key), hex);
+    }
 }
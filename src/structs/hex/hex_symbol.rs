@@ -1,9 +1,13 @@
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Deref;
 
+use crate::structs::CodecError;
+
 /******************************* HEX SYMBOL **********************************/
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(transparent)]
 pub struct HexSymbol(u8);
 
 impl HexSymbol {
@@ -11,7 +15,7 @@ impl HexSymbol {
         HexSymbol(i)
     }
 
-    fn encode_partial(input: u8) -> char {
+    pub(super) fn encode_partial(input: u8) -> char {
         match input % 16 {
             0 => '0',
             1 => '1',
@@ -33,25 +37,29 @@ impl HexSymbol {
         }
     }
 
-    fn decode_partial(input: char) -> u8 {
-        match input {
-            '0' => 0,
-            '1' => 1,
-            '2' => 2,
-            '3' => 3,
-            '4' => 4,
-            '5' => 5,
-            '6' => 6,
-            '7' => 7,
-            '8' => 8,
-            '9' => 9,
-            'a' => 10,
-            'b' => 11,
-            'c' => 12,
-            'd' => 13,
-            'e' => 14,
-            'f' => 15,
-            _ => panic!("Impossible to have an input different than '0123456789abcdef'!"),
+    /// decode an hexadecimal digit (either case), the error offset being 0
+    pub(super) fn decode_partial(input: char) -> Result<u8, CodecError> {
+        match input.to_ascii_lowercase() {
+            '0' => Ok(0),
+            '1' => Ok(1),
+            '2' => Ok(2),
+            '3' => Ok(3),
+            '4' => Ok(4),
+            '5' => Ok(5),
+            '6' => Ok(6),
+            '7' => Ok(7),
+            '8' => Ok(8),
+            '9' => Ok(9),
+            'a' => Ok(10),
+            'b' => Ok(11),
+            'c' => Ok(12),
+            'd' => Ok(13),
+            'e' => Ok(14),
+            'f' => Ok(15),
+            _ => Err(CodecError::InvalidCharacter {
+                character: input,
+                offset: 0,
+            }),
         }
     }
 
@@ -99,12 +107,14 @@ impl From<char> for HexSymbol {
     }
 }
 
-impl From<(char, char)> for HexSymbol {
-    fn from(input: (char, char)) -> Self {
-        let hv = Self::decode_partial(input.0) << 4;
-        let lv = Self::decode_partial(input.1);
+impl TryFrom<(char, char)> for HexSymbol {
+    type Error = CodecError;
+
+    fn try_from(input: (char, char)) -> Result<Self, Self::Error> {
+        let hv = Self::decode_partial(input.0)? << 4;
+        let lv = Self::decode_partial(input.1).map_err(|e| e.with_offset(1))?;
 
-        Self(hv + lv)
+        Ok(Self(hv + lv))
     }
 }
 
@@ -135,7 +145,9 @@ impl std::ops::BitXor for HexSymbol {
 
 #[cfg(test)]
 mod test {
+    use super::CodecError;
     use super::HexSymbol;
+    use std::convert::TryFrom;
 
     #[test]
     fn hex_unit_from_u8_small() {
@@ -205,12 +217,12 @@ mod test {
 
     #[test]
     fn hex_unit_from_char_tuple() {
-        assert_eq!(HexSymbol(122), HexSymbol::from(('7', 'a')));
-        assert_eq!(HexSymbol(97), HexSymbol::from(('6', '1')));
-        assert_eq!(HexSymbol(122), HexSymbol::from(('7', 'a')));
-        assert_eq!(HexSymbol(48), HexSymbol::from(('3', '0')));
-        assert_eq!(HexSymbol(65), HexSymbol::from(('4', '1')));
-        assert_eq!(HexSymbol(117), HexSymbol::from(('7', '5')));
+        assert_eq!(HexSymbol(122), HexSymbol::try_from(('7', 'a')).unwrap());
+        assert_eq!(HexSymbol(97), HexSymbol::try_from(('6', '1')).unwrap());
+        assert_eq!(HexSymbol(122), HexSymbol::try_from(('7', 'a')).unwrap());
+        assert_eq!(HexSymbol(48), HexSymbol::try_from(('3', '0')).unwrap());
+        assert_eq!(HexSymbol(65), HexSymbol::try_from(('4', '1')).unwrap());
+        assert_eq!(HexSymbol(117), HexSymbol::try_from(('7', '5')).unwrap());
     }
 
     #[test]
@@ -218,4 +230,27 @@ mod test {
         assert_eq!(HexSymbol::new(9).hamming(&HexSymbol::new(14)), 3);
         assert_eq!(HexSymbol::new(4).hamming(&HexSymbol::new(8)), 2);
     }
+
+    #[test]
+    fn hex_unit_from_uppercase_char_tuple() {
+        assert_eq!(HexSymbol(122), HexSymbol::try_from(('7', 'A')).unwrap());
+    }
+
+    #[test]
+    fn hex_unit_from_illegal_char_tuple() {
+        assert_eq!(
+            HexSymbol::try_from(('g', '0')),
+            Err(CodecError::InvalidCharacter {
+                character: 'g',
+                offset: 0
+            })
+        );
+        assert_eq!(
+            HexSymbol::try_from(('0', 'z')),
+            Err(CodecError::InvalidCharacter {
+                character: 'z',
+                offset: 1
+            })
+        );
+    }
 }
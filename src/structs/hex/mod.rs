@@ -1,5 +1,10 @@
+mod hex_blocks;
+mod hex_dump;
+mod hex_stream;
 mod hex_struct;
 mod hex_symbol;
 
+pub use hex_dump::HexDump;
+pub use hex_stream::{HexDecoder, HexEncoder};
 pub use hex_struct::Hex;
 pub use hex_symbol::HexSymbol;
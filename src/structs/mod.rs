@@ -1,8 +1,31 @@
+mod ascii85;
+mod base32;
+mod base58;
 mod base64;
+mod codec;
 mod dictionary;
+mod encoding;
+mod error;
+mod frequency;
 mod hex;
+mod ngram;
+mod text;
+mod xor;
 
-pub use base64::Base64;
+pub use ascii85::{Ascii85, Z85};
+pub use base32::Base32;
+pub use base58::Base58;
+pub use base64::{
+    Base64, Base64Alphabet, Base64Decoder, Base64Display, Base64Encoder, MIME_LINE_WIDTH,
+};
+pub use codec::{transcode, Codec};
 pub use dictionary::Dictionary;
+pub use encoding::{candidates, decode_detected, detect, load_file, Detection, Encoding};
+pub use error::{CodecError, CorpusError, XorError};
+pub use frequency::ByteFrequency;
 pub use hex::Hex;
 pub use hex::HexSymbol;
+pub use hex::{HexDecoder, HexDump, HexEncoder};
+pub use ngram::NgramModel;
+pub use text::{is_printable_ascii, is_text};
+pub use xor::{rolling_xor_in_place, xor_in_place};
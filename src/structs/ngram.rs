@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::structs::CorpusError;
+
+/// english corpus embedded in the binary, used by `NgramModel::english`
+const ENGLISH_CORPUS: &str = include_str!("../../data/corpus/english.txt");
+
+/// order of the english model: quadgrams
+const ENGLISH_ORDER: usize = 4;
+
+/// count given to the n-grams never seen in the corpus
+const UNSEEN_COUNT: f64 = 0.01;
+
+/// case-fold the byte, all whitespace being a single space
+fn normalize(byte: u8) -> u8 {
+    if byte.is_ascii_whitespace() {
+        b' '
+    } else {
+        byte.to_ascii_lowercase()
+    }
+}
+
+/******************************* NGRAM MODEL *********************************/
+
+/// log-probabilities of the n-grams of a corpus, up to a given order (e.g. 4 for
+/// quadgrams), case insensitive
+#[derive(Debug, PartialEq, Clone)]
+pub struct NgramModel {
+    order: usize,
+    /// occurrences of the n-grams of each order, starting with unigrams
+    counts: Vec<HashMap<Vec<u8>, u32>>,
+    /// number of n-grams of each order in the corpus
+    totals: Vec<u32>,
+}
+
+impl NgramModel {
+    /// model trained on an english corpus embedded in the binary, of order 4
+    pub fn english() -> Self {
+        Self::train(ENGLISH_ORDER, ENGLISH_CORPUS.as_bytes())
+            .expect("the order of the english model is positive")
+    }
+
+    /// count the n-grams of the corpus, up to the given order, which must be positive
+    pub fn train(order: usize, corpus: &[u8]) -> Result<Self, CorpusError> {
+        if order == 0 {
+            return Err(CorpusError::InvalidOrder);
+        }
+
+        let corpus: Vec<u8> = corpus.iter().map(|b| normalize(*b)).collect();
+        let mut counts = vec![HashMap::new(); order];
+        let mut totals = vec![0; order];
+        for n in 1..=order {
+            for ngram in corpus.windows(n) {
+                *counts[n - 1].entry(ngram.to_vec()).or_insert(0) += 1;
+                totals[n - 1] += 1;
+            }
+        }
+
+        Ok(Self {
+            order,
+            counts,
+            totals,
+        })
+    }
+
+    /// train a model on the whole content of a reader, which is not read if the order is
+    /// not positive
+    pub fn from_reader<R: Read>(order: usize, mut reader: R) -> Result<Self, CorpusError> {
+        if order == 0 {
+            return Err(CorpusError::InvalidOrder);
+        }
+
+        let mut corpus = Vec::new();
+        reader.read_to_end(&mut corpus)?;
+        Self::train(order, &corpus)
+    }
+
+    /// train a model on the content of a file
+    pub fn from_file<P: AsRef<Path>>(order: usize, path: P) -> Result<Self, CorpusError> {
+        if order == 0 {
+            return Err(CorpusError::InvalidOrder);
+        }
+
+        Self::from_reader(order, File::open(path)?)
+    }
+
+    /// highest order of the n-grams of the model
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// natural logarithm of the probability of a n-gram, case insensitive
+    ///
+    /// n-grams never seen in the corpus (or longer than the order) get a probability
+    /// far below the one of the rarest n-gram seen
+    pub fn log_probability(&self, ngram: &[u8]) -> f64 {
+        let n = ngram.len();
+        if n == 0 || n > self.order {
+            return f64::NEG_INFINITY;
+        }
+
+        let ngram: Vec<u8> = ngram.iter().map(|b| normalize(*b)).collect();
+        self.normalized_log_probability(&ngram)
+    }
+
+    /// same as `log_probability`, the n-gram being already normalized and of a valid length
+    fn normalized_log_probability(&self, ngram: &[u8]) -> f64 {
+        let n = ngram.len();
+        let count = self.counts[n - 1]
+            .get(ngram)
+            .map_or(UNSEEN_COUNT, |count| *count as f64);
+        let total = (self.totals[n - 1] as f64).max(1.0);
+        (count / total).ln()
+    }
+
+    /// mean log-probability of the n-grams of the input: the higher, the more likely the
+    /// input is to be text of the corpus language
+    ///
+    /// inputs shorter than the order are scored with n-grams of their own length
+    pub fn score(&self, input: &[u8]) -> f64 {
+        if input.is_empty() {
+            return 0.0;
+        }
+
+        let input: Vec<u8> = input.iter().map(|b| normalize(*b)).collect();
+        let n = self.order.min(input.len());
+        let windows = input.windows(n);
+        let count = windows.len() as f64;
+        windows
+            .map(|ngram| self.normalized_log_probability(ngram))
+            .sum::<f64>()
+            / count
+    }
+}
+
+impl Default for NgramModel {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::NgramModel;
+    use crate::structs::CorpusError;
+    use crate::testing::{NOISE, TEXT};
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn english_beats_noise() {
+        let english = NgramModel::english();
+        assert_eq!(english.order(), 4);
+        assert!(english.score(TEXT) > english.score(NOISE));
+    }
+
+    #[test]
+    fn short_inputs() {
+        // columns of a transposed ciphertext only hold a few bytes
+        let english = NgramModel::english();
+        assert!(english.score(b"the ") > english.score(b"qzx "));
+        assert!(english.score(b"he") > english.score(b"\x00\x7f"));
+        assert!(english.score(b"e") > english.score(b"z"));
+        assert_eq!(english.score(b""), 0.0);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let english = NgramModel::english();
+        assert_eq!(english.score(b"THE\nNATION"), english.score(b"the nation"));
+    }
+
+    #[test]
+    fn trained_on_reader() {
+        let model = NgramModel::from_reader(2, Cursor::new("abab abab")).unwrap();
+        assert_eq!(model.order(), 2);
+        assert_eq!(model.log_probability(b"ab"), (4.0f64 / 8.0).ln());
+        assert!(model.log_probability(b"ba") > model.log_probability(b"aa"));
+        assert_eq!(model.log_probability(b"aba"), f64::NEG_INFINITY);
+        assert!(model.score(b"abab") > model.score(b"baaa"));
+
+        assert_eq!(
+            NgramModel::from_file(2, "data/corpus/missing.txt"),
+            Err(CorpusError::Io(ErrorKind::NotFound))
+        );
+    }
+
+    #[test]
+    fn invalid_order() {
+        assert_eq!(
+            NgramModel::train(0, b"abab"),
+            Err(CorpusError::InvalidOrder)
+        );
+        assert_eq!(
+            NgramModel::from_reader(0, Cursor::new("abab")),
+            Err(CorpusError::InvalidOrder)
+        );
+        assert_eq!(
+            NgramModel::from_file(0, "data/corpus/missing.txt"),
+            Err(CorpusError::InvalidOrder)
+        );
+    }
+}
@@ -0,0 +1,33 @@
+/// whether the byte can be displayed as is: an ascii graphic character or a space
+///
+/// unlike `HexSymbol::is_printable`, DEL and non-ascii bytes are not printable
+pub fn is_printable_ascii(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' '
+}
+
+/// whether the byte can appear in a plain text: a printable byte, or an ascii whitespace
+/// (tab, line feed, form feed or carriage return)
+pub fn is_text(byte: u8) -> bool {
+    is_printable_ascii(byte) || byte.is_ascii_whitespace()
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{is_printable_ascii, is_text};
+
+    #[test]
+    fn printable() {
+        assert!(b"az AZ09~!".iter().all(|b| is_printable_ascii(*b)));
+        assert!(b"\t\n\r\x00\x1f\x7f\x80\xff"
+            .iter()
+            .all(|b| !is_printable_ascii(*b)));
+    }
+
+    #[test]
+    fn text() {
+        assert!(b"az AZ09~!\t\n\x0c\r".iter().all(|b| is_text(*b)));
+        assert!(b"\x00\x0b\x1f\x7f\x80\xff".iter().all(|b| !is_text(*b)));
+    }
+}
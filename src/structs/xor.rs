@@ -0,0 +1,62 @@
+use crate::structs::XorError;
+
+/// XOR the buffer with another input of the same length
+pub fn xor_in_place(buffer: &mut [u8], other: &[u8]) -> Result<(), XorError> {
+    if buffer.len() != other.len() {
+        return Err(XorError::LengthMismatch {
+            left: buffer.len(),
+            right: other.len(),
+        });
+    }
+
+    for (byte, other) in buffer.iter_mut().zip(other.iter()) {
+        *byte ^= other;
+    }
+    Ok(())
+}
+
+/// XOR the buffer with a key repeated as many times as needed
+pub fn rolling_xor_in_place(buffer: &mut [u8], key: &[u8]) -> Result<(), XorError> {
+    if key.is_empty() {
+        return Err(XorError::EmptyKey);
+    }
+
+    for (byte, key) in buffer.iter_mut().zip(key.iter().cycle()) {
+        *byte ^= key;
+    }
+    Ok(())
+}
+
+/***************************** TESTS *****************************************/
+
+#[cfg(test)]
+mod test {
+    use super::{rolling_xor_in_place, xor_in_place};
+    use crate::structs::XorError;
+
+    #[test]
+    fn fixed_xor() {
+        let mut buffer = *b"abc";
+        assert_eq!(xor_in_place(&mut buffer, &[0x20, 0x20, 0x00]), Ok(()));
+        assert_eq!(&buffer, b"ABc");
+
+        assert_eq!(
+            xor_in_place(&mut buffer, b"ab"),
+            Err(XorError::LengthMismatch { left: 3, right: 2 })
+        );
+        assert_eq!(&buffer, b"ABc");
+    }
+
+    #[test]
+    fn rolling_xor() {
+        let mut buffer = *b"abcde";
+        assert_eq!(rolling_xor_in_place(&mut buffer, &[0x20, 0x00]), Ok(()));
+        assert_eq!(&buffer, b"AbCdE");
+
+        assert_eq!(
+            rolling_xor_in_place(&mut buffer, &[]),
+            Err(XorError::EmptyKey)
+        );
+        assert_eq!(rolling_xor_in_place(&mut [], b"key"), Ok(()));
+    }
+}
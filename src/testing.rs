@@ -0,0 +1,17 @@
+//! helpers shared by the tests of several modules
+
+use std::io::Read;
+
+use crate::structs::CodecError;
+
+/// error raised when reading the whole output of a streaming decoder
+pub(crate) fn read_error<R: Read>(mut decoder: R) -> CodecError {
+    let mut decoded = Vec::new();
+    decoder.read_to_end(&mut decoded).unwrap_err().into()
+}
+
+/// english sample that the text scorers should prefer over `NOISE`
+pub(crate) const TEXT: &[u8] = b"Cooking MC's like a pound of bacon";
+
+/// printable bytes that read as no english at all
+pub(crate) const NOISE: &[u8] = b"Xtt|~y0]X;o0}~|t0q0`{|0{|0sqvt";